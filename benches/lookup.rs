@@ -0,0 +1,119 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+// Compares error name lookups through the dense tables used by `Arch` with
+// lookups through a `match` on the error number, which is how the names
+// were looked up before the tables were added.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use linux_errno::arch::Arch;
+use linux_errno::Error;
+
+// Expands the architecture files into `match`-based lookup functions.
+macro_rules! errno_constants {
+	( $( #[doc = $doc:literal] $name:ident = $value:literal , )+ ) => {
+		pub fn err_name(errno: u16) -> Option<&'static str> {
+			match errno {
+			$(
+				$value => Some(stringify!($name)),
+			)*
+				_ => None,
+			}
+		}
+	}
+}
+
+macro_rules! errno_aliases {
+	( $( $tt:tt )* ) => {};
+}
+
+#[path = "../linux-errno/linux-errno_generic.rs"]
+mod match_generic;
+
+#[path = "../linux-errno/linux-errno_mips.rs"]
+mod match_mips;
+
+// Every error number up to the largest dense table, and the sparse `EDQUOT`
+// of mips.
+fn errors() -> Vec<Error> {
+	(1..=260).chain(Some(1133)).filter_map(Error::new).collect()
+}
+
+fn bench_err_name(c: &mut Criterion) {
+	let errors = errors();
+	let mut group = c.benchmark_group("err_name");
+	group.bench_function("match/generic", |b| {
+		b.iter(|| {
+			for err in &errors {
+				black_box(match_generic::err_name(black_box(err.get())));
+			}
+		})
+	});
+	group.bench_function("table/generic", |b| {
+		b.iter(|| {
+			for &err in &errors {
+				black_box(Arch::Generic.err_name(black_box(err)));
+			}
+		})
+	});
+	group.bench_function("match/mips", |b| {
+		b.iter(|| {
+			for err in &errors {
+				black_box(match_mips::err_name(black_box(err.get())));
+			}
+		})
+	});
+	group.bench_function("table/mips", |b| {
+		b.iter(|| {
+			for &err in &errors {
+				black_box(Arch::Mips.err_name(black_box(err)));
+			}
+		})
+	});
+	group.bench_function("table/powerpc", |b| {
+		b.iter(|| {
+			for &err in &errors {
+				black_box(Arch::Powerpc.err_name(black_box(err)));
+			}
+		})
+	});
+	group.finish();
+}
+
+fn bench_debug(c: &mut Criterion) {
+	use core::fmt::Write;
+
+	struct Sink;
+
+	impl Write for Sink {
+		fn write_str(&mut self, s: &str) -> core::fmt::Result {
+			black_box(s);
+			Ok(())
+		}
+	}
+
+	let errors = errors();
+	c.bench_function("debug", |b| {
+		b.iter(|| {
+			for &err in &errors {
+				let _ = write!(Sink, "{:?}", black_box(err));
+			}
+		})
+	});
+}
+
+criterion_group!(benches, bench_err_name, bench_debug);
+criterion_main!(benches);
@@ -0,0 +1,359 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Look up Linux error numbers by number, name, or description.
+//!
+//! ```text
+//! $ errno 2
+//! ENOENT 2 No such file or directory
+//! $ errno --arch mips 1133
+//! EDQUOT 1133 Quota exceeded
+//! $ errno --search 'too many'
+//! EMFILE 24 Too many open files
+//! ...
+//! ```
+
+use std::process::ExitCode;
+use std::{env, io};
+
+use linux_errno::arch::{self, Arch};
+use linux_errno::{kernel, Error};
+
+const USAGE: &str = "\
+Usage: errno [--arch ARCH] NUMBER|NAME|TEXT...
+       errno [--arch ARCH] --search TEXT...
+       errno [--arch ARCH] --list
+       errno --diff ARCH,ARCH
+
+Error names are matched ignoring case. Arguments that are not an error
+number or name are matched against error descriptions, ignoring case.
+Kernel-internal error numbers, such as ERESTARTSYS (512), are recognized on
+every architecture.
+
+Options:
+  -a, --arch ARCH       Use the error numbers of ARCH
+  -d, --diff ARCH,ARCH  List error numbers that differ between two arches
+  -l, --list            List all error numbers
+  -s, --search          Match all arguments against error descriptions
+  -h, --help            Show this help
+
+Architectures: generic, alpha, arm, m68k, mips, parisc, powerpc, riscv32,
+s390x, sparc, x86
+";
+
+enum Mode {
+	Lookup,
+	Search,
+	List,
+	Diff(Arch, Arch),
+}
+
+fn main() -> ExitCode {
+	let mut out = io::stdout().lock();
+	match run(&mut out, env::args().skip(1).collect()) {
+		Ok(true) => ExitCode::SUCCESS,
+		Ok(false) => ExitCode::FAILURE,
+		Err(msg) => {
+			eprintln!("errno: {}", msg);
+			eprint!("{}", USAGE);
+			ExitCode::from(2)
+		},
+	}
+}
+
+fn run(out: &mut impl io::Write, args: Vec<String>) -> Result<bool, String> {
+	let mut arch = Arch::TARGET;
+	let mut mode = Mode::Lookup;
+	let mut words = Vec::new();
+
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"-a" | "--arch" => {
+				let name = args.next().ok_or("--arch requires a value")?;
+				arch = parse_arch(&name)?;
+			},
+			"-d" | "--diff" => {
+				let value = args.next().ok_or("--diff requires a value")?;
+				let (a, b) = value
+					.split_once(',')
+					.ok_or("--diff requires two comma-separated arches")?;
+				mode = Mode::Diff(parse_arch(a)?, parse_arch(b)?);
+			},
+			"-l" | "--list" => mode = Mode::List,
+			"-s" | "--search" => mode = Mode::Search,
+			"-h" | "--help" => {
+				let _ = write!(out, "{}", USAGE);
+				return Ok(true);
+			},
+			"--" => words.extend(args.by_ref()),
+			_ if arg.starts_with('-') && arg.len() > 1 => {
+				return Err(format!("unknown option {:?}", arg));
+			},
+			_ => words.push(arg),
+		}
+	}
+
+	let found = match mode {
+		Mode::List => {
+			for err in errors(arch) {
+				print_error(out, arch, err, None);
+			}
+			true
+		},
+		Mode::Diff(a, b) => {
+			print_diff(out, a, b);
+			true
+		},
+		Mode::Search if words.is_empty() => {
+			return Err("--search requires an argument".into());
+		},
+		Mode::Lookup if words.is_empty() => {
+			return Err("missing argument".into());
+		},
+		Mode::Search => {
+			let mut found = true;
+			for word in &words {
+				found &= search(out, arch, word);
+			}
+			found
+		},
+		Mode::Lookup => {
+			let mut found = true;
+			for word in &words {
+				found &= lookup(out, arch, word);
+			}
+			found
+		},
+	};
+	Ok(found)
+}
+
+fn parse_arch(name: &str) -> Result<Arch, String> {
+	Arch::from_name(name).ok_or_else(|| format!("unknown arch {:?}", name))
+}
+
+fn errors(arch: Arch) -> impl Iterator<Item = Error> {
+	(1..=0xFFF)
+		.filter_map(Error::new)
+		.filter(move |&err| arch.err_name(err).is_some())
+}
+
+fn lookup(out: &mut impl io::Write, arch: Arch, word: &str) -> bool {
+	if let Ok(errno) = word.parse::<u16>() {
+		return match Error::new(errno) {
+			Some(err) if err_name(arch, err).is_some() => {
+				print_error(out, arch, err, None);
+				true
+			},
+			_ => {
+				eprintln!("errno: unknown error number {}", errno);
+				false
+			},
+		};
+	}
+	// Error names are upper case, such as `ENOENT` or `ERESTART_RESTARTBLOCK`.
+	let name = word.to_ascii_uppercase();
+	if let Some(err) = err_from_name(arch, &name) {
+		print_error(out, arch, err, Some(&name));
+		return true;
+	}
+	search(out, arch, word)
+}
+
+// The name of an error number on `arch`, or of a kernel-internal error number.
+fn err_name(arch: Arch, err: Error) -> Option<&'static str> {
+	arch.err_name(err).or_else(|| kernel::name(err))
+}
+
+fn err_message(arch: Arch, err: Error) -> Option<&'static str> {
+	arch.err_message(err).or_else(|| kernel::message(err))
+}
+
+fn err_from_name(arch: Arch, name: &str) -> Option<Error> {
+	arch.err_from_name(name).or_else(|| {
+		(1..=0xFFF)
+			.filter_map(Error::new)
+			.find(|&err| kernel::name(err) == Some(name))
+	})
+}
+
+fn search(out: &mut impl io::Write, arch: Arch, word: &str) -> bool {
+	let needle = word.to_ascii_lowercase();
+	let mut found = false;
+	for err in errors(arch) {
+		let message = arch.err_message(err).unwrap_or("");
+		if message.to_ascii_lowercase().contains(&needle) {
+			print_error(out, arch, err, None);
+			found = true;
+		}
+	}
+	if !found {
+		eprintln!("errno: no error matches {:?}", word);
+	}
+	found
+}
+
+fn print_error(
+	out: &mut impl io::Write,
+	arch: Arch,
+	err: Error,
+	name: Option<&str>,
+) {
+	let name = name.or_else(|| err_name(arch, err)).unwrap_or("?");
+	let message = err_message(arch, err).unwrap_or("");
+	let _ = writeln!(out, "{} {} {}", name, err.get(), message);
+}
+
+fn print_diff(out: &mut impl io::Write, a: Arch, b: Arch) {
+	let _ = writeln!(out, "{:<16} {:>7} {:>7}", "NAME", a.name(), b.name());
+	for (name, in_a, in_b) in arch::diff(a, b) {
+		let _ = writeln!(
+			out,
+			"{:<16} {:>7} {:>7}",
+			name,
+			format_errno(in_a),
+			format_errno(in_b),
+		);
+	}
+}
+
+fn format_errno(err: Option<Error>) -> String {
+	match err {
+		Some(err) => err.get().to_string(),
+		None => "-".into(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn errno(args: &[&str]) -> Result<(bool, String), String> {
+		let mut out = Vec::new();
+		let args = args.iter().map(|arg| arg.to_string()).collect();
+		let found = run(&mut out, args)?;
+		Ok((found, String::from_utf8(out).unwrap()))
+	}
+
+	#[test]
+	fn lookup_number() {
+		assert_eq!(
+			errno(&["2"]),
+			Ok((true, "ENOENT 2 No such file or directory\n".into())),
+		);
+		assert_eq!(
+			errno(&["--arch", "mips", "1133"]),
+			Ok((true, "EDQUOT 1133 Quota exceeded\n".into())),
+		);
+		assert_eq!(errno(&["4000"]), Ok((false, "".into())));
+		assert_eq!(errno(&["0"]), Ok((false, "".into())));
+	}
+
+	#[test]
+	fn lookup_name() {
+		assert_eq!(
+			errno(&["-a", "generic", "EWOULDBLOCK"]),
+			Ok((true, "EWOULDBLOCK 11 Try again\n".into())),
+		);
+		assert_eq!(
+			errno(&["-a", "sparc", "EDEADLOCK"]),
+			Ok((true, "EDEADLOCK 108 File locking deadlock error\n".into())),
+		);
+	}
+
+	#[test]
+	fn lookup_kernel() {
+		let expect = "ERESTARTSYS 512 Restart system call if the signal \
+			handler allows it\n";
+		assert_eq!(errno(&["512"]), Ok((true, expect.into())));
+		assert_eq!(errno(&["-a", "mips", "512"]), Ok((true, expect.into())));
+		assert_eq!(errno(&["ERESTARTSYS"]), Ok((true, expect.into())));
+		let expect = "EJUKEBOX 528 Request initiated, but will not complete \
+			before timeout\n";
+		assert_eq!(errno(&["-a", "sparc", "528"]), Ok((true, expect.into())));
+		// 520 is not used by the kernel.
+		assert_eq!(errno(&["520"]), Ok((false, "".into())));
+	}
+
+	#[test]
+	fn lookup_name_ignore_case() {
+		assert_eq!(
+			errno(&["enoent"]),
+			Ok((true, "ENOENT 2 No such file or directory\n".into())),
+		);
+		assert_eq!(
+			errno(&["-a", "mips", "EdQuot"]),
+			Ok((true, "EDQUOT 1133 Quota exceeded\n".into())),
+		);
+		assert_eq!(
+			errno(&["-a", "generic", "ewouldblock"]),
+			Ok((true, "EWOULDBLOCK 11 Try again\n".into())),
+		);
+		let expect = "ERESTART_RESTARTBLOCK 516 Restart by calling \
+			sys_restart_syscall\n";
+		assert_eq!(
+			errno(&["erestart_restartblock"]),
+			Ok((true, expect.into())),
+		);
+	}
+
+	#[test]
+	fn search() {
+		let expect = "\
+			EMFILE 24 Too many open files\n\
+			EMLINK 31 Too many links\n";
+		assert_eq!(
+			errno(&["-a", "generic", "-s", "too many open", "too many links"]),
+			Ok((true, expect.into())),
+		);
+		assert_eq!(
+			errno(&["-a", "generic", "too many links"]),
+			Ok((true, "EMLINK 31 Too many links\n".into())),
+		);
+		assert_eq!(errno(&["no such error"]), Ok((false, "".into())));
+	}
+
+	#[test]
+	fn list() {
+		let (found, out) = errno(&["--list", "--arch", "generic"]).unwrap();
+		assert!(found);
+		assert!(out.starts_with("EPERM 1 Operation not permitted\n"));
+		assert_eq!(out.lines().count(), errors(Arch::Generic).count());
+	}
+
+	#[test]
+	fn diff() {
+		let expect = "\
+			NAME                 x86 powerpc\n\
+			EDEADLOCK             35      58\n";
+		assert_eq!(
+			errno(&["--diff", "x86,powerpc"]),
+			Ok((true, expect.into())),
+		);
+		assert_eq!(errno(&["--diff", "x86,x86"]).unwrap().1.lines().count(), 1);
+	}
+
+	#[test]
+	fn usage_errors() {
+		assert!(errno(&[]).is_err());
+		assert!(errno(&["--search"]).is_err());
+		assert!(errno(&["--arch"]).is_err());
+		assert!(errno(&["--arch", "vax", "2"]).is_err());
+		assert!(errno(&["--diff", "x86"]).is_err());
+		assert!(errno(&["--bogus"]).is_err());
+		assert_eq!(errno(&["--", "-1"]), Ok((false, "".into())));
+	}
+}
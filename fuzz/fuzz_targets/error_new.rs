@@ -0,0 +1,43 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+#![no_main]
+
+use core::convert::TryFrom;
+
+use libfuzzer_sys::fuzz_target;
+use linux_errno::Error;
+
+fuzz_target!(|input: (u16, i32)| {
+	let (errno, neg_errno) = input;
+
+	match Error::new(errno) {
+		Some(err) => {
+			assert!(errno > 0 && errno < 0x1000);
+			assert_eq!(err.get(), errno);
+			assert_eq!(Error::from_negated(-i32::from(errno)), Some(err));
+			assert_eq!(Error::try_from(errno).ok(), Some(err));
+		},
+		None => {
+			assert!(errno == 0 || errno >= 0x1000);
+			assert!(Error::try_from(errno).is_err());
+		},
+	}
+
+	match Error::from_negated(neg_errno) {
+		Some(err) => assert_eq!(-i32::from(err.get()), neg_errno),
+		None => assert!(neg_errno >= 0 || neg_errno <= -0x1000),
+	}
+});
@@ -0,0 +1,69 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use linux_errno::arch::Arch;
+use linux_errno::{Error, MessageFormat};
+
+const FORMATS: [MessageFormat; 4] = [
+	MessageFormat::Full,
+	MessageFormat::Name,
+	MessageFormat::Number,
+	MessageFormat::Strerror,
+];
+
+fn check(u: &mut Unstructured<'_>) -> Result<()> {
+	let arch = Arch::TARGET;
+	let err = if u.arbitrary()? {
+		arch.arbitrary_error(u)?
+	} else {
+		u.arbitrary::<Error>()?
+	};
+	let format = *u.choose(&FORMATS)?;
+	let len = u.int_in_range(0..=128)?;
+
+	let mut out = [0u8; 128];
+	let out_len = err.write_to_format(&mut out, format);
+	assert!(out_len > 0 && out_len <= out.len());
+	assert!(core::str::from_utf8(&out[..out_len]).is_ok());
+
+	// A truncated write is a prefix of the full output.
+	let mut buf = [0xFFu8; 128];
+	let buf_len = err.write_to_format(&mut buf[..len], format);
+	assert_eq!(buf_len, out_len.min(len));
+	assert_eq!(&buf[..buf_len], &out[..buf_len]);
+	assert!(buf[len..].iter().all(|&b| b == 0xFF));
+
+	let display = format!("{}", err);
+	let mut strerror = [0u8; 128];
+	let strerror_len =
+		err.write_to_format(&mut strerror, MessageFormat::Strerror);
+	assert_eq!(display.as_bytes(), &strerror[..strerror_len]);
+
+	let mut full = [0u8; 128];
+	let full_len = err.write_to(&mut full);
+	let alternate = format!("{:#}", err);
+	assert_eq!(alternate.as_bytes(), &full[..full_len]);
+
+	let _ = format!("{:?} {:+?} {:#?}", err, err, err);
+	Ok(())
+}
+
+fuzz_target!(|data: &[u8]| {
+	let _ = check(&mut Unstructured::new(data));
+});
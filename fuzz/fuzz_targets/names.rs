@@ -0,0 +1,53 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use linux_errno::arch::Arch;
+use linux_errno::{parse, ErrnoName};
+
+fuzz_target!(|input: (Arch, &str)| {
+	let (arch, text) = input;
+
+	if let Some(err) = arch.err_from_name(text) {
+		let name = arch.err_name(err).unwrap();
+		assert_eq!(arch.err_from_name(name), Some(err));
+	}
+
+	if let Some(err) = arch.err_from_message(text) {
+		let message = arch.err_message(err).unwrap();
+		assert!(message.eq_ignore_ascii_case(text));
+	}
+
+	if let Some(name) = ErrnoName::from_name(text) {
+		assert_eq!(name.as_str(), text);
+		assert_eq!(ErrnoName::from_id(name as u16), Some(name));
+	}
+
+	let mut end = 0;
+	for m in parse::errors(text.as_bytes(), arch) {
+		assert!(end <= m.span.start);
+		assert!(m.span.start < m.span.end);
+		assert!(m.span.end <= text.len());
+		match m.name {
+			// Kernel-internal error numbers are also named.
+			Some(_) if m.error.get() >= 512 => {},
+			Some(name) => assert_eq!(arch.err_from_name(name), Some(m.error)),
+			None => assert_eq!(arch.err_name(m.error), None),
+		}
+		end = m.span.end;
+	}
+});
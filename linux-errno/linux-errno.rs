@@ -22,6 +22,10 @@
 
 #![no_std]
 
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use core::convert::TryFrom;
 use core::{fmt, num};
 
 /// Type for error numbers returned from Linux system calls.
@@ -31,6 +35,15 @@ use core::{fmt, num};
 /// library.
 ///
 /// [`posix-errno`]: https://crates.io/crates/posix-errno
+///
+/// # Negative numbers
+///
+/// Linux system calls return errors as negated error numbers, such as `-2`
+/// for `ENOENT`. The `Error` type always stores the positive error number.
+///
+/// * Negative integers never compare equal to an `Error`, and converting a
+///   negative integer with `TryFrom` fails with [`OutOfRange`].
+/// * Negated error numbers can be converted with [`Error::from_negated`].
 #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Error(num::NonZeroU16);
 
@@ -59,6 +72,17 @@ impl Error {
 		Error(num::NonZeroU16::new_unchecked(errno))
 	}
 
+	/// Create a new error from a negated error number, such as the `-2`
+	/// returned by a system call that failed with `ENOENT`. If outside the
+	/// range `(-4096, -1]`, returns `None`.
+	#[inline]
+	pub const fn from_negated(neg_errno: i32) -> Option<Error> {
+		if neg_errno >= 0 || neg_errno <= -0x1000 {
+			return errno_out_of_range();
+		}
+		Error::new((-neg_errno) as u16)
+	}
+
 	/// Returns the error number as a primitive `u16`.
 	#[inline]
 	pub const fn get(&self) -> u16 {
@@ -78,6 +102,47 @@ const fn errno_out_of_range() -> Option<Error> {
 	None
 }
 
+/// The error type returned when converting an out-of-range integer to an
+/// [`Error`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct OutOfRange(());
+
+impl fmt::Display for OutOfRange {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("error number out of range [1, 4096)")
+	}
+}
+
+#[cfg(feature = "core-error")]
+impl core::error::Error for OutOfRange {}
+
+#[cfg(all(feature = "std", not(feature = "core-error")))]
+impl std::error::Error for OutOfRange {}
+
+macro_rules! impl_try_from {
+	($t:ty) => {
+		impl TryFrom<$t> for Error {
+			type Error = OutOfRange;
+
+			#[inline]
+			fn try_from(errno: $t) -> Result<Error, OutOfRange> {
+				match u16::try_from(errno) {
+					Ok(errno) => Error::new(errno).ok_or(OutOfRange(())),
+					Err(_) => Err(OutOfRange(())),
+				}
+			}
+		}
+	};
+}
+
+impl_try_from!(i32);
+impl_try_from!(i64);
+impl_try_from!(isize);
+impl_try_from!(u16);
+impl_try_from!(u32);
+impl_try_from!(u64);
+impl_try_from!(usize);
+
 impl From<Error> for u16 {
 	#[inline]
 	fn from(err: Error) -> u16 {
@@ -172,56 +237,58 @@ impl fmt::UpperHex for Error {
 impl PartialEq<i16> for Error {
 	#[inline]
 	fn eq(&self, other: &i16) -> bool {
-		(*other as u16) == self.0.get()
+		*other > 0 && (*other as u16) == self.0.get()
 	}
 }
 
 impl PartialEq<Error> for i16 {
 	#[inline]
 	fn eq(&self, other: &Error) -> bool {
-		(*self as u16) == other.0.get()
+		*self > 0 && (*self as u16) == other.0.get()
 	}
 }
 
 impl PartialEq<isize> for Error {
 	#[inline]
 	fn eq(&self, other: &isize) -> bool {
-		(*other as usize) == usize::from(self.0.get())
+		*other > 0 && (*other as usize) == usize::from(self.0.get())
 	}
 }
 
 impl PartialEq<Error> for isize {
 	#[inline]
 	fn eq(&self, other: &Error) -> bool {
-		(*self as usize) == usize::from(other.0.get())
+		*self > 0 && (*self as usize) == usize::from(other.0.get())
 	}
 }
 
 impl PartialEq<num::NonZeroI16> for Error {
 	#[inline]
 	fn eq(&self, other: &num::NonZeroI16) -> bool {
-		(other.get() as u16) == self.0.get()
+		other.get() > 0 && (other.get() as u16) == self.0.get()
 	}
 }
 
 impl PartialEq<Error> for num::NonZeroI16 {
 	#[inline]
 	fn eq(&self, other: &Error) -> bool {
-		(self.get() as u16) == other.0.get()
+		self.get() > 0 && (self.get() as u16) == other.0.get()
 	}
 }
 
 impl PartialEq<num::NonZeroIsize> for Error {
 	#[inline]
 	fn eq(&self, other: &num::NonZeroIsize) -> bool {
-		(other.get() as usize) == usize::from(self.0.get())
+		other.get() > 0
+			&& (other.get() as usize) == usize::from(self.0.get())
 	}
 }
 
 impl PartialEq<Error> for num::NonZeroIsize {
 	#[inline]
 	fn eq(&self, other: &Error) -> bool {
-		(self.get() as usize) == usize::from(other.0.get())
+		self.get() > 0
+			&& (self.get() as usize) == usize::from(other.0.get())
 	}
 }
 
@@ -275,29 +342,150 @@ impl_partial_eq_nonzero!(num::NonZeroU32);
 impl_partial_eq_nonzero!(num::NonZeroU64);
 impl_partial_eq_nonzero!(num::NonZeroUsize);
 
+#[derive(Clone, Copy)]
+pub(crate) struct Entry {
+	pub(crate) name: &'static str,
+	pub(crate) error: Error,
+	pub(crate) message: &'static str,
+}
+
+impl Entry {
+	const fn new(name: &'static str, error: Error, doc: &'static str) -> Entry {
+		let mut message = doc.as_bytes();
+		while let [b' ', rest @ ..] = message {
+			message = rest;
+		}
+		let message = match core::str::from_utf8(message) {
+			Ok(message) => message,
+			Err(_) => panic!(),
+		};
+		Entry { name, error, message }
+	}
+}
+
 macro_rules! errno_constants {
-	( $( $(#[$meta:meta])* $name:ident = $value:literal , )+ ) => {
+	(
+		$( #[doc = $enum_doc:literal] )+
+		enum KnownError;
+		$( #[doc = $doc:literal] $name:ident = $value:literal , )+
+	) => {
 		use core::fmt;
 
 		$(
-			$(#[$meta])*
+			#[doc = $doc]
 			pub const $name: $crate::Error = unsafe {
 				$crate::Error::new_unchecked($value)
 			};
 		)*
 
+		pub(crate) const ERRNOS: &[$crate::Entry] = &[
+		$(
+			$crate::Entry::new(stringify!($name), $name, $doc),
+		)*
+		];
+
+		$( #[doc = $enum_doc] )+
+		#[allow(clippy::upper_case_acronyms, non_camel_case_types)]
+		#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+		#[repr(u16)]
+		pub enum KnownError {
+		$(
+			#[doc = $doc]
+			$name = $value,
+		)*
+		}
+
+		impl From<KnownError> for $crate::Error {
+			#[inline]
+			fn from(err: KnownError) -> $crate::Error {
+				unsafe { $crate::Error::new_unchecked(err as u16) }
+			}
+		}
+
+		impl core::convert::TryFrom<$crate::Error> for KnownError {
+			/// The error number that has no `KnownError` variant.
+			type Error = $crate::Error;
+
+			#[inline]
+			fn try_from(err: $crate::Error) -> Result<KnownError, $crate::Error> {
+				match err.0.get() {
+				$(
+					$value => Ok(KnownError::$name),
+				)*
+					_ => Err(err),
+				}
+			}
+		}
+
+		pub(crate) const TABLE: $crate::table::Table = errno_table!(ERRNOS);
+
 		#[inline]
 		pub(crate) const fn err_name(err: $crate::Error) -> Option<&'static str> {
-			match err.0.get() {
-			$(
-				$value => Some(stringify!($name)),
-			)*
-				_ => None,
-			}
+			TABLE.err_name(err)
+		}
+
+		#[inline]
+		pub(crate) const fn err_message(err: $crate::Error) -> Option<&'static str> {
+			TABLE.err_message(err)
+		}
+	};
+	( $( #[doc = $doc:literal] $name:ident = $value:literal , )+ ) => {
+		errno_constants! {
+			/// The error numbers of this architecture, as an enum.
+			///
+			/// Unlike the [`Error`](crate::Error) constants, a `KnownError`
+			/// can be matched exhaustively. Aliases such as `EWOULDBLOCK`
+			/// are associated constants.
+			enum KnownError;
+			$( #[doc = $doc] $name = $value, )+
+		}
+	};
+}
+
+macro_rules! errno_table {
+	($entries:expr) => {{
+		const ENTRIES: &[$crate::Entry] = $entries;
+		const DENSE: [u8; $crate::table::dense_len(ENTRIES)] =
+			$crate::table::dense(ENTRIES);
+		const SPARSE: [$crate::Entry; $crate::table::sparse_len(ENTRIES)] =
+			$crate::table::sparse(ENTRIES);
+		$crate::table::Table::new(ENTRIES, &DENSE, &SPARSE)
+	}};
+}
+
+macro_rules! errno_aliases {
+	( $( #[doc = $doc:literal] $name:ident = $target:ident , )+ ) => {
+		$(
+			#[doc = $doc]
+			pub const $name: $crate::Error = $target;
+		)*
+
+		impl KnownError {
+		$(
+			#[doc = $doc]
+			pub const $name: KnownError = KnownError::$target;
+		)*
 		}
+
+		pub(crate) const ALIASES: &[$crate::Entry] = &[
+		$(
+			$crate::Entry {
+				name: stringify!($name),
+				error: $target,
+				message: match err_message($target) {
+					Some(message) => message,
+					None => "",
+				},
+			},
+		)*
+		];
 	}
 }
 
+#[path = "linux-errno_table.rs"]
+mod table;
+
+#[macro_use]
 #[path = "linux-errno_generic.rs"]
 mod arch_generic;
 
@@ -310,9 +498,20 @@ mod arch_mips;
 #[path = "linux-errno_parisc.rs"]
 mod arch_parisc;
 
+#[path = "linux-errno_powerpc.rs"]
+mod arch_powerpc;
+
 #[path = "linux-errno_sparc.rs"]
 mod arch_sparc;
 
+/// Error numbers used internally by the Linux kernel.
+///
+/// These error numbers are defined in `include/linux/errno.h` and are the
+/// same on all architectures. They should never be returned to user space,
+/// but they do appear in some protocols and kernel interfaces.
+#[path = "linux-errno_kernel.rs"]
+pub mod kernel;
+
 /// Linux error numbers for specific target architectures.
 pub mod arch {
 	/// Linux error numbers for the `alpha` architecture.
@@ -354,20 +553,7 @@ pub mod arch {
 		doc,
 	))]
 	pub mod powerpc {
-		pub use crate::arch_generic::*;
-
-		/// File locking deadlock error
-		pub const EDEADLOCK: crate::Error = unsafe {
-			crate::Error::new_unchecked(58)
-		};
-
-		#[inline]
-		pub(crate) const fn err_name(err: crate::Error) -> Option<&'static str> {
-			if err.0.get() == EDEADLOCK.0.get() {
-				return Some(stringify!("EDEADLOCK"));
-			}
-			crate::arch_generic::err_name(err)
-		}
+		pub use crate::arch_powerpc::*;
 	}
 
 	/// Linux error numbers for the `parisc` architecture.
@@ -411,6 +597,356 @@ pub mod arch {
 	pub mod x86 {
 		pub use crate::arch_generic::*;
 	}
+
+	use crate::table::Table;
+	use crate::{Entry, Error};
+	use crate::{
+		arch_alpha,
+		arch_generic,
+		arch_mips,
+		arch_parisc,
+		arch_powerpc,
+		arch_sparc,
+	};
+
+	/// Architectures with distinct sets of Linux error numbers.
+	///
+	/// Unlike the architecture modules, which are only available when
+	/// building for the matching target (or when building documentation),
+	/// the error number tables for every `Arch` are available on all targets.
+	#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+	pub enum Arch {
+		/// The error numbers defined in `asm-generic`, which are used by
+		/// most architectures.
+		Generic,
+		/// The `alpha` architecture.
+		Alpha,
+		/// The `arm` and `aarch64` architectures.
+		Arm,
+		/// The `m68k` architecture.
+		M68k,
+		/// The `mips` and `mips64` architectures.
+		Mips,
+		/// The `parisc` architecture.
+		Parisc,
+		/// The `powerpc` and `powerpc64` architectures.
+		Powerpc,
+		/// The `riscv32` and `riscv64` architectures.
+		Riscv32,
+		/// The `s390x` architecture.
+		S390x,
+		/// The `sparc` and `sparc64` architectures.
+		Sparc,
+		/// The `x86` and `x86_64` architectures.
+		X86,
+	}
+
+	impl Arch {
+		/// The architecture of the current compilation target.
+		#[cfg(target_arch = "alpha")]
+		pub const TARGET: Arch = Arch::Alpha;
+
+		/// The architecture of the current compilation target.
+		#[cfg(any(
+			target_arch = "arm",
+			target_arch = "aarch64",
+		))]
+		pub const TARGET: Arch = Arch::Arm;
+
+		/// The architecture of the current compilation target.
+		#[cfg(target_arch = "m68k")]
+		pub const TARGET: Arch = Arch::M68k;
+
+		/// The architecture of the current compilation target.
+		#[cfg(any(
+			target_arch = "mips",
+			target_arch = "mips64",
+		))]
+		pub const TARGET: Arch = Arch::Mips;
+
+		/// The architecture of the current compilation target.
+		#[cfg(target_arch = "parisc")]
+		pub const TARGET: Arch = Arch::Parisc;
+
+		/// The architecture of the current compilation target.
+		#[cfg(any(
+			target_arch = "powerpc",
+			target_arch = "powerpc64",
+		))]
+		pub const TARGET: Arch = Arch::Powerpc;
+
+		/// The architecture of the current compilation target.
+		#[cfg(any(
+			target_arch = "riscv32",
+			target_arch = "riscv64",
+		))]
+		pub const TARGET: Arch = Arch::Riscv32;
+
+		/// The architecture of the current compilation target.
+		#[cfg(target_arch = "s390x")]
+		pub const TARGET: Arch = Arch::S390x;
+
+		/// The architecture of the current compilation target.
+		#[cfg(any(
+			target_arch = "sparc",
+			target_arch = "sparc64",
+		))]
+		pub const TARGET: Arch = Arch::Sparc;
+
+		/// The architecture of the current compilation target.
+		#[cfg(any(
+			target_arch = "x86",
+			target_arch = "x86_64",
+		))]
+		pub const TARGET: Arch = Arch::X86;
+
+		/// All architectures, in declaration order.
+		pub const ALL: [Arch; 11] = [
+			Arch::Generic,
+			Arch::Alpha,
+			Arch::Arm,
+			Arch::M68k,
+			Arch::Mips,
+			Arch::Parisc,
+			Arch::Powerpc,
+			Arch::Riscv32,
+			Arch::S390x,
+			Arch::Sparc,
+			Arch::X86,
+		];
+
+		/// Returns the name of this architecture, which is the name of its
+		/// module in [`arch`](crate::arch) (or `"generic"`).
+		pub const fn name(self) -> &'static str {
+			match self {
+				Arch::Generic => "generic",
+				Arch::Alpha => "alpha",
+				Arch::Arm => "arm",
+				Arch::M68k => "m68k",
+				Arch::Mips => "mips",
+				Arch::Parisc => "parisc",
+				Arch::Powerpc => "powerpc",
+				Arch::Riscv32 => "riscv32",
+				Arch::S390x => "s390x",
+				Arch::Sparc => "sparc",
+				Arch::X86 => "x86",
+			}
+		}
+
+		/// Returns the architecture with the given name, as returned by
+		/// [`Arch::name`].
+		pub fn from_name(name: &str) -> Option<Arch> {
+			Arch::ALL.iter().copied().find(|arch| arch.name() == name)
+		}
+
+		/// Returns the name of an error number on this architecture, such
+		/// as `"ENOENT"`.
+		#[inline]
+		pub const fn err_name(self, err: Error) -> Option<&'static str> {
+			self.table().err_name(err)
+		}
+
+		/// Returns the description of an error number on this architecture,
+		/// such as `"No such file or directory"`.
+		#[inline]
+		pub const fn err_message(self, err: Error) -> Option<&'static str> {
+			self.table().err_message(err)
+		}
+
+		/// Returns the error number with the given name on this
+		/// architecture. Aliases such as `"EWOULDBLOCK"` are recognized.
+		pub fn err_from_name(self, name: &str) -> Option<Error> {
+			self.entries()
+				.find(|entry| entry.name == name)
+				.map(|entry| entry.error)
+		}
+
+		/// Returns the error number with the given description on this
+		/// architecture. The comparison ignores ASCII case.
+		pub fn err_from_message(self, message: &str) -> Option<Error> {
+			self.entries()
+				.find(|entry| entry.message.eq_ignore_ascii_case(message))
+				.map(|entry| entry.error)
+		}
+
+		/// Translates an error number from this architecture to another
+		/// architecture, matching them by name.
+		///
+		/// The kernel-internal error numbers of the [`kernel`](crate::kernel)
+		/// module are the same on every architecture, and are returned
+		/// unchanged.
+		///
+		/// Returns `None` if `err` is not defined on this architecture, or if
+		/// the target architecture has no error number with the same name.
+		pub fn translate(self, err: Error, to: Arch) -> Option<Error> {
+			if crate::kernel::err_name(err).is_some() {
+				return Some(err);
+			}
+			self.entries()
+				.filter(|entry| entry.error == err)
+				.find_map(|entry| to.err_from_name(entry.name))
+		}
+
+		const fn table(self) -> Table {
+			match self {
+				Arch::Alpha => arch_alpha::TABLE,
+				Arch::Mips => arch_mips::TABLE,
+				Arch::Parisc => arch_parisc::TABLE,
+				Arch::Powerpc => arch_powerpc::TABLE,
+				Arch::Sparc => arch_sparc::TABLE,
+				_ => arch_generic::TABLE,
+			}
+		}
+
+		fn tables(self) -> &'static [&'static [Entry]] {
+			match self {
+				Arch::Alpha => &[arch_alpha::ERRNOS, arch_alpha::ALIASES],
+				Arch::Mips => &[arch_mips::ERRNOS, arch_mips::ALIASES],
+				Arch::Parisc => &[arch_parisc::ERRNOS, arch_parisc::ALIASES],
+				Arch::Powerpc => &[arch_powerpc::ERRNOS, arch_powerpc::ALIASES],
+				Arch::Sparc => &[arch_sparc::ERRNOS, arch_sparc::ALIASES],
+				_ => &[arch_generic::ERRNOS, arch_generic::ALIASES],
+			}
+		}
+
+		pub(crate) fn entries(self) -> impl Iterator<Item = &'static Entry> {
+			self.tables().iter().flat_map(|table| table.iter())
+		}
+
+		// The entries of the error numbers, without aliases. Each error number
+		// appears once.
+		#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+		pub(crate) fn errnos(self) -> &'static [Entry] {
+			self.tables()[0]
+		}
+
+		// Like `entries()`, but without entries shadowed by an earlier entry
+		// of the same name.
+		fn unique_entries(self) -> impl Iterator<Item = &'static Entry> {
+			self.entries().filter(move |entry| {
+				self.err_from_name(entry.name) == Some(entry.error)
+			})
+		}
+	}
+
+	/// Returns an iterator over the error names whose error numbers differ
+	/// between two architectures.
+	///
+	/// Each item contains the name, its error number on `a`, and its error
+	/// number on `b`. Names that are defined on only one of the
+	/// architectures have `None` for the other. Aliases such as
+	/// `EWOULDBLOCK` are included.
+	///
+	/// The names defined on `a` are returned first, followed by the names
+	/// defined only on `b`.
+	///
+	/// ```
+	/// # use linux_errno::arch::{self, Arch};
+	/// let (name, x86, powerpc) = arch::diff(Arch::X86, Arch::Powerpc)
+	///     .next()
+	///     .unwrap();
+	/// assert_eq!(name, "EDEADLOCK");
+	/// assert_eq!(x86.map(|err| err.get()), Some(35));
+	/// assert_eq!(powerpc.map(|err| err.get()), Some(58));
+	/// ```
+	pub fn diff(
+		a: Arch,
+		b: Arch,
+	) -> impl Iterator<Item = (&'static str, Option<Error>, Option<Error>)> {
+		let in_a = a.unique_entries().filter_map(move |entry| {
+			let in_b = b.err_from_name(entry.name);
+			if in_b == Some(entry.error) {
+				return None;
+			}
+			Some((entry.name, Some(entry.error), in_b))
+		});
+		let only_in_b = b.unique_entries().filter_map(move |entry| {
+			if a.err_from_name(entry.name).is_some() {
+				return None;
+			}
+			Some((entry.name, None, Some(entry.error)))
+		});
+		in_a.chain(only_in_b)
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use std::vec::Vec;
+
+		use super::*;
+
+		type Diff = (&'static str, Option<u16>, Option<u16>);
+
+		fn diff_numbers(a: Arch, b: Arch) -> Vec<Diff> {
+			diff(a, b)
+				.map(|(name, a, b)| {
+					(name, a.map(|e| e.get()), b.map(|e| e.get()))
+				})
+				.collect()
+		}
+
+		#[test]
+		fn same_numbering() {
+			for &arch in Arch::ALL.iter() {
+				assert_eq!(diff(arch, arch).count(), 0, "{:?}", arch);
+			}
+			assert_eq!(diff(Arch::Generic, Arch::X86).count(), 0);
+			assert_eq!(diff(Arch::Arm, Arch::Riscv32).count(), 0);
+		}
+
+		#[test]
+		fn powerpc() {
+			assert_eq!(
+				diff_numbers(Arch::X86, Arch::Powerpc),
+				[("EDEADLOCK", Some(35), Some(58))],
+			);
+			assert_eq!(
+				diff_numbers(Arch::Powerpc, Arch::X86),
+				[("EDEADLOCK", Some(58), Some(35))],
+			);
+		}
+
+		#[test]
+		fn names_on_one_arch() {
+			let diffs = diff_numbers(Arch::Generic, Arch::Mips);
+			assert!(diffs.contains(&("EDQUOT", Some(122), Some(1133))));
+			assert_eq!(
+				diffs[diffs.len() - 2..],
+				[("EINIT", None, Some(141)), ("EREMDEV", None, Some(142))],
+			);
+
+			let diffs = diff_numbers(Arch::Mips, Arch::Generic);
+			assert!(diffs.contains(&("EINIT", Some(141), None)));
+			assert!(diffs.contains(&("EREMDEV", Some(142), None)));
+		}
+
+		#[test]
+		fn consistent() {
+			for &a in Arch::ALL.iter() {
+				for &b in Arch::ALL.iter() {
+					let diffs = diff_numbers(a, b);
+					let mut reversed = diff_numbers(b, a);
+					for (name, in_a, in_b) in &diffs {
+						assert_ne!(in_a, in_b);
+						assert_eq!(
+							diffs.iter().filter(|d| d.0 == *name).count(),
+							1,
+							"{} {:?} {:?}",
+							name,
+							a,
+							b,
+						);
+						let pos = reversed
+							.iter()
+							.position(|d| *d == (*name, *in_b, *in_a))
+							.unwrap();
+						reversed.remove(pos);
+					}
+					assert_eq!(reversed, []);
+				}
+			}
+		}
+	}
 }
 
 #[cfg(target_arch = "alpha")]
@@ -461,15 +997,172 @@ use crate::arch::x86 as target;
 #[doc(inline)]
 pub use crate::target::*;
 
-impl fmt::Debug for Error {
+#[path = "linux-errno_nfs.rs"]
+pub mod nfs;
+
+#[path = "linux-errno_9p.rs"]
+pub mod p9;
+
+#[cfg(feature = "arbitrary")]
+#[path = "linux-errno_arbitrary.rs"]
+mod arbitrary;
+
+#[path = "linux-errno_context.rs"]
+mod context;
+
+pub use crate::context::ErrorContext;
+
+#[path = "linux-errno_counters.rs"]
+mod counters;
+
+pub use crate::counters::ErrnoCounters;
+
+#[path = "linux-errno_io_uring.rs"]
+mod io_uring;
+
+pub use crate::io_uring::CqeResult;
+
+#[cfg(feature = "libc")]
+#[path = "linux-errno_libc.rs"]
+mod libc;
+
+#[path = "linux-errno_message.rs"]
+mod message;
+
+pub use crate::message::MessageFormat;
+
+#[path = "linux-errno_name.rs"]
+mod name;
+
+pub use crate::name::ErrnoName;
+
+#[path = "linux-errno_netlink.rs"]
+pub mod netlink;
+
+#[path = "linux-errno_packed.rs"]
+mod packed;
+
+pub use crate::packed::{ErrOrPtr, ErrOrUsize};
+
+#[path = "linux-errno_parse.rs"]
+pub mod parse;
+
+#[path = "linux-errno_portable.rs"]
+pub mod portable;
+
+#[cfg(feature = "proptest")]
+#[path = "linux-errno_proptest.rs"]
+mod proptest;
+
+#[path = "linux-errno_status.rs"]
+mod status;
+
+#[cfg(feature = "std")]
+pub use crate::status::Exit;
+
+#[cfg(all(
+	feature = "syscall",
+	any(
+		target_arch = "aarch64",
+		target_arch = "riscv64",
+		target_arch = "x86_64",
+	),
+))]
+#[path = "linux-errno_syscall.rs"]
+pub mod syscall;
+
+#[path = "linux-errno_wasi.rs"]
+pub mod wasi;
+
+#[path = "linux-errno_windows.rs"]
+pub mod windows;
+
+// Returns the name of an error number on the current target, including the
+// kernel-internal error numbers.
+pub(crate) const fn target_err_name(err: Error) -> Option<&'static str> {
+	match arch::Arch::TARGET.err_name(err) {
+		Some(name) => Some(name),
+		None => kernel::err_name(err),
+	}
+}
+
+// Returns the description of an error number on the current target,
+// including the kernel-internal error numbers.
+pub(crate) const fn target_err_message(err: Error) -> Option<&'static str> {
+	match arch::Arch::TARGET.err_message(err) {
+		Some(message) => Some(message),
+		None => kernel::err_message(err),
+	}
+}
+
+// An error name, or `E?1234` for an unknown error number.
+struct ErrName(Error);
+
+impl fmt::Debug for ErrName {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		match crate::target::err_name(*self) {
+		match target_err_name(self.0) {
 			Some(name) => f.write_str(name),
-			_ => f.debug_tuple("Error").field(&self.0.get()).finish(),
+			None => write!(f, "E?{}", self.0.get()),
+		}
+	}
+}
+
+/// Formats the error number by name, such as `ENOENT`. Error numbers that
+/// are not defined on the current target are formatted as `E?1234`. The
+/// names of kernel-internal error numbers (see [`kernel`]) are used.
+///
+/// Alternate formats include more detail:
+///
+/// * `{:+?}` appends the number to known names, as in `ENOENT(2)`.
+/// * `{:#?}` formats a struct with the name, number, description, and the
+///   architecture that the name was resolved for.
+impl fmt::Debug for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.alternate() {
+			let mut s = f.debug_struct("Error");
+			s.field("name", &ErrName(*self));
+			s.field("number", &self.0.get());
+			s.field("message", &target_err_message(*self));
+			s.field("arch", &arch::Arch::TARGET);
+			return s.finish();
+		}
+		match target_err_name(*self) {
+			Some(name) if f.sign_plus() => {
+				write!(f, "{}({})", name, self.0.get())
+			},
+			_ => ErrName(*self).fmt(f),
+		}
+	}
+}
+
+/// Formats the description of the error number, such as
+/// `No such file or directory`. The alternate format `{:#}` also includes
+/// the name and number, as in `ENOENT (2): No such file or directory`.
+///
+/// The output is the same as that of [`Error::write_to_format`] with
+/// [`MessageFormat::Strerror`] or [`MessageFormat::Full`].
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let format = if f.alternate() {
+			MessageFormat::Full
+		} else {
+			MessageFormat::Strerror
+		};
+		let mut buf = [0u8; 128];
+		let len = self.write_to_format(&mut buf, format);
+		match core::str::from_utf8(&buf[..len]) {
+			Ok(s) => f.pad(s),
+			Err(_) => Err(fmt::Error),
 		}
 	}
 }
 
+#[cfg(feature = "core-error")]
+impl core::error::Error for Error {}
+
+#[cfg(all(feature = "std", not(feature = "core-error")))]
+impl std::error::Error for Error {}
+
 #[cfg(feature = "posix-traits")]
 const fn from_posix(err: posix_errno::Error) -> Option<Error> {
 	use posix_errno::Error as P;
@@ -574,3 +1267,175 @@ impl PartialEq<Error> for posix_errno::Error {
 		from_posix(*self) == Some(*other)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::format;
+
+	use super::*;
+
+	#[test]
+	fn debug() {
+		assert_eq!(format!("{:?}", target::ENOENT), "ENOENT");
+		assert_eq!(format!("{:+?}", target::ENOENT), "ENOENT(2)");
+		assert_eq!(format!("{:?}", kernel::ERESTARTSYS), "ERESTARTSYS");
+		assert_eq!(format!("{:+?}", kernel::ERESTARTSYS), "ERESTARTSYS(512)");
+
+		let unknown = Error::new(4000).unwrap();
+		assert_eq!(format!("{:?}", unknown), "E?4000");
+		assert_eq!(format!("{:+?}", unknown), "E?4000");
+	}
+
+	#[test]
+	fn debug_alternate() {
+		let arch = format!("    arch: {:?},", arch::Arch::TARGET);
+		let expect = [
+			"Error {",
+			"    name: ENOENT,",
+			"    number: 2,",
+			"    message: Some(",
+			"        \"No such file or directory\",",
+			"    ),",
+			&arch,
+			"}",
+		];
+		assert_eq!(format!("{:#?}", target::ENOENT), expect.join("\n"));
+
+		let expect = [
+			"Error {",
+			"    name: E?4000,",
+			"    number: 4000,",
+			"    message: None,",
+			&arch,
+			"}",
+		];
+		let unknown = Error::new(4000).unwrap();
+		assert_eq!(format!("{:#?}", unknown), expect.join("\n"));
+	}
+
+	#[test]
+	fn display() {
+		let err = target::ENOENT;
+		assert_eq!(format!("{}", err), "No such file or directory");
+		let full = "ENOENT (2): No such file or directory";
+		assert_eq!(format!("{:#}", err), full);
+		assert_eq!(format!("{:>12}", target::EIO), "   I/O error");
+		assert_eq!(format!("[{:^11}]", target::EIO), "[ I/O error ]");
+
+		let unknown = Error::new(4000).unwrap();
+		assert_eq!(format!("{}", unknown), "Unknown error 4000");
+		assert_eq!(format!("{:#}", unknown), "Unknown error 4000");
+	}
+
+	#[test]
+	fn new() {
+		assert_eq!(Error::new(0), None);
+		assert_eq!(Error::new(1).map(|e| e.get()), Some(1));
+		assert_eq!(Error::new(0xFFF).map(|e| e.get()), Some(0xFFF));
+		assert_eq!(Error::new(0x1000), None);
+		assert_eq!(Error::new(u16::MAX), None);
+	}
+
+	#[test]
+	fn from_negated() {
+		assert_eq!(Error::from_negated(-2), Some(target::ENOENT));
+		assert_eq!(Error::from_negated(-1), Error::new(1));
+		assert_eq!(Error::from_negated(-4095), Error::new(4095));
+		assert_eq!(Error::from_negated(-4096), None);
+		assert_eq!(Error::from_negated(0), None);
+		assert_eq!(Error::from_negated(2), None);
+		assert_eq!(Error::from_negated(i32::MIN), None);
+		assert_eq!(Error::from_negated(i32::MAX), None);
+	}
+
+	#[test]
+	fn try_from() {
+		let max = Error::new(4095).unwrap();
+		let out_of_range = Err(OutOfRange(()));
+
+		assert_eq!(Error::try_from(2i32), Ok(target::ENOENT));
+		assert_eq!(Error::try_from(4095i32), Ok(max));
+		assert_eq!(Error::try_from(4096i32), out_of_range);
+		assert_eq!(Error::try_from(0i32), out_of_range);
+		assert_eq!(Error::try_from(-2i32), out_of_range);
+		assert_eq!(Error::try_from(i32::MIN), out_of_range);
+		assert_eq!(Error::try_from(i32::MAX), out_of_range);
+
+		assert_eq!(Error::try_from(4095i64), Ok(max));
+		assert_eq!(Error::try_from(i64::MIN), out_of_range);
+		assert_eq!(Error::try_from(0x1_0002i64), out_of_range);
+		assert_eq!(Error::try_from(-1isize), out_of_range);
+		assert_eq!(Error::try_from(4095u16), Ok(max));
+		assert_eq!(Error::try_from(u16::MAX), out_of_range);
+		assert_eq!(Error::try_from(0x1_0002u32), out_of_range);
+		assert_eq!(Error::try_from(u64::MAX), out_of_range);
+		assert_eq!(Error::try_from(usize::MAX), out_of_range);
+		assert_eq!(Error::try_from(0usize), out_of_range);
+	}
+
+	#[test]
+	fn into_integers() {
+		let err = target::ENOENT;
+		assert_eq!(u16::from(err), 2);
+		assert_eq!(u32::from(err), 2);
+		assert_eq!(i32::from(err), 2);
+		assert_eq!(u64::from(err), 2);
+		assert_eq!(i64::from(err), 2);
+		assert_eq!(num::NonZeroI32::from(err).get(), 2);
+		assert_eq!(err, 2i32);
+		assert_eq!(2usize, err);
+		assert_ne!(err, -2i32);
+		assert_ne!(-2isize, err);
+	}
+
+	#[test]
+	fn known_error() {
+		let known = KnownError::try_from(target::ENOENT);
+		assert_eq!(known, Ok(KnownError::ENOENT));
+		assert_eq!(Error::from(KnownError::ENOENT), target::ENOENT);
+		assert_eq!(KnownError::EWOULDBLOCK, KnownError::EAGAIN);
+
+		let unknown = Error::new(4000).unwrap();
+		assert_eq!(KnownError::try_from(unknown), Err(unknown));
+		assert_eq!(
+			KnownError::try_from(kernel::ERESTARTSYS),
+			Err(kernel::ERESTARTSYS),
+		);
+
+		for err in (1..0x1000).filter_map(Error::new) {
+			let known = KnownError::try_from(err);
+			assert_eq!(known.is_ok(), target::err_name(err).is_some());
+			if let Ok(known) = known {
+				assert_eq!(Error::from(known), err);
+			}
+		}
+	}
+
+	#[test]
+	fn kernel_known_error() {
+		use kernel::KnownError;
+
+		assert_eq!(
+			KnownError::try_from(kernel::ERESTARTSYS),
+			Ok(KnownError::ERESTARTSYS),
+		);
+		assert_eq!(Error::from(KnownError::EJUKEBOX), kernel::EJUKEBOX);
+		assert_eq!(KnownError::try_from(target::ENOENT), Err(target::ENOENT));
+
+		for err in (1..0x1000).filter_map(Error::new) {
+			let known = KnownError::try_from(err);
+			assert_eq!(known.is_ok(), kernel::err_name(err).is_some());
+		}
+	}
+
+	#[test]
+	fn kernel_lookup() {
+		assert_eq!(kernel::name(kernel::ERESTARTSYS), Some("ERESTARTSYS"));
+		assert_eq!(
+			kernel::message(kernel::ENOGRACE),
+			Some("NFS file lock reclaim refused"),
+		);
+		assert_eq!(kernel::name(target::ENOENT), None);
+		assert_eq!(kernel::message(Error::new(520).unwrap()), None);
+	}
+}
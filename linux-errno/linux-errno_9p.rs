@@ -0,0 +1,455 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Error reporting in the 9P2000.L and 9P2000.u file protocols.
+//!
+//! The `Rlerror` message of 9P2000.L carries a Linux error number, using the
+//! numbering of the server's architecture. When the client and server
+//! architectures differ (for example a `mips` server and an `x86` client),
+//! the error number must be translated. Servers may also normalize error
+//! numbers to the [`Arch::Generic`] numbering, which clients can then decode
+//! without knowing the server's architecture.
+//!
+//! The `Rerror` message of 9P2000.u carries an error string (`ename`), which
+//! can be resolved to an error number with [`from_ename`].
+
+use core::convert::TryFrom;
+
+use crate::Error;
+use crate::arch::Arch;
+use crate::target;
+
+/// The 9P2000.L message type of `Rlerror`.
+pub const RLERROR: u8 = 7;
+
+/// A 9P2000.L `Rlerror` message.
+///
+/// The wire format is `size[4] Rlerror tag[2] ecode[4]`, with all fields in
+/// little-endian byte order.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rlerror {
+	/// The tag of the request that failed.
+	pub tag: u16,
+	/// The error number, in the numbering of the server's architecture.
+	pub ecode: u32,
+}
+
+impl Rlerror {
+	/// The size of an encoded `Rlerror` message, including the size field.
+	pub const SIZE: usize = 11;
+
+	/// Creates an `Rlerror` for an error number of the current target,
+	/// translated to the numbering of the `wire` architecture.
+	///
+	/// Kernel-internal error numbers, such as
+	/// [`ERESTARTSYS`](crate::kernel::ERESTARTSYS), are the same on every
+	/// architecture and are not translated. Returns `None` if the `wire`
+	/// architecture has no equivalent error number.
+	pub fn new(tag: u16, err: Error, wire: Arch) -> Option<Rlerror> {
+		let ecode = Arch::TARGET.translate(err, wire)?;
+		Some(Rlerror {
+			tag,
+			ecode: ecode.into(),
+		})
+	}
+
+	/// Returns the error number of this `Rlerror`, translated from the
+	/// numbering of the `wire` architecture to the current target.
+	///
+	/// Kernel-internal error numbers are not translated. Returns `None` if
+	/// `ecode` is not a valid error number on the `wire` architecture, or if
+	/// the current target has no equivalent.
+	pub fn error(&self, wire: Arch) -> Option<Error> {
+		let err = Error::new(u16::try_from(self.ecode).ok()?)?;
+		wire.translate(err, Arch::TARGET)
+	}
+
+	/// Encodes this `Rlerror` in the 9P2000.L wire format.
+	pub const fn encode(&self) -> [u8; Rlerror::SIZE] {
+		let size = (Rlerror::SIZE as u32).to_le_bytes();
+		let tag = self.tag.to_le_bytes();
+		let ecode = self.ecode.to_le_bytes();
+		[
+			size[0], size[1], size[2], size[3],
+			RLERROR,
+			tag[0], tag[1],
+			ecode[0], ecode[1], ecode[2], ecode[3],
+		]
+	}
+
+	/// Decodes an `Rlerror` from the 9P2000.L wire format.
+	///
+	/// Returns `None` if `buf` does not start with a complete `Rlerror`
+	/// message.
+	pub fn decode(buf: &[u8]) -> Option<Rlerror> {
+		if buf.len() < Rlerror::SIZE || buf[4] != RLERROR {
+			return None;
+		}
+		let size = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+		if size != (Rlerror::SIZE as u32) {
+			return None;
+		}
+		Some(Rlerror {
+			tag: u16::from_le_bytes([buf[5], buf[6]]),
+			ecode: u32::from_le_bytes([buf[7], buf[8], buf[9], buf[10]]),
+		})
+	}
+}
+
+/// Resolves a 9P2000.u error string to an error number of the current
+/// target.
+///
+/// Like the Linux kernel's 9P client, the error string is first matched
+/// against the strings sent by common 9P2000.u servers: the `strerror()`
+/// descriptions of glibc, such as `"Resource temporarily unavailable"`, and
+/// the errors of Plan 9 file servers, such as `"file does not exist"`. It is
+/// then matched against the error descriptions of the kernel headers and
+/// against the error names, so `"Try again"` and `"EAGAIN"` are also
+/// recognized. All comparisons ignore ASCII case.
+pub fn from_ename(ename: &str) -> Option<Error> {
+	ENAMES
+		.iter()
+		.find(|(name, _)| name.eq_ignore_ascii_case(ename))
+		.map(|&(_, err)| err)
+		.or_else(|| Arch::TARGET.err_from_message(ename))
+		.or_else(|| Arch::TARGET.err_from_name(ename))
+}
+
+// https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/net/9p/error.c?h=v5.19
+
+#[rustfmt::skip]
+const ENAMES: &[(&str, Error)] = &[
+	// The descriptions of glibc `strerror()`.
+	("Operation not permitted", target::EPERM),
+	("No such file or directory", target::ENOENT),
+	("No such process", target::ESRCH),
+	("Interrupted system call", target::EINTR),
+	("Input/output error", target::EIO),
+	("No such device or address", target::ENXIO),
+	("Argument list too long", target::E2BIG),
+	("Exec format error", target::ENOEXEC),
+	("Bad file descriptor", target::EBADF),
+	("No child processes", target::ECHILD),
+	("Resource temporarily unavailable", target::EAGAIN),
+	("Cannot allocate memory", target::ENOMEM),
+	("Permission denied", target::EACCES),
+	("Bad address", target::EFAULT),
+	("Block device required", target::ENOTBLK),
+	("Device or resource busy", target::EBUSY),
+	("File exists", target::EEXIST),
+	("Invalid cross-device link", target::EXDEV),
+	("No such device", target::ENODEV),
+	("Not a directory", target::ENOTDIR),
+	("Is a directory", target::EISDIR),
+	("Invalid argument", target::EINVAL),
+	("Too many open files in system", target::ENFILE),
+	("Too many open files", target::EMFILE),
+	("Inappropriate ioctl for device", target::ENOTTY),
+	("Text file busy", target::ETXTBSY),
+	("File too large", target::EFBIG),
+	("No space left on device", target::ENOSPC),
+	("Illegal seek", target::ESPIPE),
+	("Read-only file system", target::EROFS),
+	("Too many links", target::EMLINK),
+	("Broken pipe", target::EPIPE),
+	("Numerical argument out of domain", target::EDOM),
+	("Numerical result out of range", target::ERANGE),
+	("Resource deadlock avoided", target::EDEADLK),
+	("File name too long", target::ENAMETOOLONG),
+	("No locks available", target::ENOLCK),
+	("Function not implemented", target::ENOSYS),
+	("Directory not empty", target::ENOTEMPTY),
+	("Too many levels of symbolic links", target::ELOOP),
+	("No message of desired type", target::ENOMSG),
+	("Identifier removed", target::EIDRM),
+	("Channel number out of range", target::ECHRNG),
+	("Level 2 not synchronized", target::EL2NSYNC),
+	("Level 3 halted", target::EL3HLT),
+	("Level 3 reset", target::EL3RST),
+	("Link number out of range", target::ELNRNG),
+	("Protocol driver not attached", target::EUNATCH),
+	("No CSI structure available", target::ENOCSI),
+	("Level 2 halted", target::EL2HLT),
+	("Invalid exchange", target::EBADE),
+	("Invalid request descriptor", target::EBADR),
+	("Exchange full", target::EXFULL),
+	("No anode", target::ENOANO),
+	("Invalid request code", target::EBADRQC),
+	("Invalid slot", target::EBADSLT),
+	("Bad font file format", target::EBFONT),
+	("Device not a stream", target::ENOSTR),
+	("No data available", target::ENODATA),
+	("Timer expired", target::ETIME),
+	("Out of streams resources", target::ENOSR),
+	("Machine is not on the network", target::ENONET),
+	("Package not installed", target::ENOPKG),
+	("Object is remote", target::EREMOTE),
+	("Link has been severed", target::ENOLINK),
+	("Advertise error", target::EADV),
+	("Srmount error", target::ESRMNT),
+	("Communication error on send", target::ECOMM),
+	("Protocol error", target::EPROTO),
+	("Multihop attempted", target::EMULTIHOP),
+	("RFS specific error", target::EDOTDOT),
+	("Bad message", target::EBADMSG),
+	("Value too large for defined data type", target::EOVERFLOW),
+	("Name not unique on network", target::ENOTUNIQ),
+	("File descriptor in bad state", target::EBADFD),
+	("Remote address changed", target::EREMCHG),
+	("Can not access a needed shared library", target::ELIBACC),
+	("Accessing a corrupted shared library", target::ELIBBAD),
+	(".lib section in a.out corrupted", target::ELIBSCN),
+	("Attempting to link in too many shared libraries", target::ELIBMAX),
+	("Cannot exec a shared library directly", target::ELIBEXEC),
+	("Invalid or incomplete multibyte or wide character", target::EILSEQ),
+	("Interrupted system call should be restarted", target::ERESTART),
+	("Streams pipe error", target::ESTRPIPE),
+	("Too many users", target::EUSERS),
+	("Socket operation on non-socket", target::ENOTSOCK),
+	("Destination address required", target::EDESTADDRREQ),
+	("Message too long", target::EMSGSIZE),
+	("Protocol wrong type for socket", target::EPROTOTYPE),
+	("Protocol not available", target::ENOPROTOOPT),
+	("Protocol not supported", target::EPROTONOSUPPORT),
+	("Socket type not supported", target::ESOCKTNOSUPPORT),
+	("Operation not supported", target::EOPNOTSUPP),
+	("Protocol family not supported", target::EPFNOSUPPORT),
+	("Address family not supported by protocol", target::EAFNOSUPPORT),
+	("Address already in use", target::EADDRINUSE),
+	("Cannot assign requested address", target::EADDRNOTAVAIL),
+	("Network is down", target::ENETDOWN),
+	("Network is unreachable", target::ENETUNREACH),
+	("Network dropped connection on reset", target::ENETRESET),
+	("Software caused connection abort", target::ECONNABORTED),
+	("Connection reset by peer", target::ECONNRESET),
+	("No buffer space available", target::ENOBUFS),
+	("Transport endpoint is already connected", target::EISCONN),
+	("Transport endpoint is not connected", target::ENOTCONN),
+	("Cannot send after transport endpoint shutdown", target::ESHUTDOWN),
+	("Too many references: cannot splice", target::ETOOMANYREFS),
+	("Connection timed out", target::ETIMEDOUT),
+	("Connection refused", target::ECONNREFUSED),
+	("Host is down", target::EHOSTDOWN),
+	("No route to host", target::EHOSTUNREACH),
+	("Operation already in progress", target::EALREADY),
+	("Operation now in progress", target::EINPROGRESS),
+	("Stale file handle", target::ESTALE),
+	("Structure needs cleaning", target::EUCLEAN),
+	("Not a XENIX named type file", target::ENOTNAM),
+	("No XENIX semaphores available", target::ENAVAIL),
+	("Is a named type file", target::EISNAM),
+	("Remote I/O error", target::EREMOTEIO),
+	("Disk quota exceeded", target::EDQUOT),
+	("No medium found", target::ENOMEDIUM),
+	("Wrong medium type", target::EMEDIUMTYPE),
+	("Operation canceled", target::ECANCELED),
+	("Required key not available", target::ENOKEY),
+	("Key has expired", target::EKEYEXPIRED),
+	("Key has been revoked", target::EKEYREVOKED),
+	("Key was rejected by service", target::EKEYREJECTED),
+	("Owner died", target::EOWNERDEAD),
+	("State not recoverable", target::ENOTRECOVERABLE),
+	("Operation not possible due to RF-kill", target::ERFKILL),
+	("Memory page has hardware error", target::EHWPOISON),
+	// The errors of Plan 9 file servers, such as fossil and vacfs, and of
+	// u9fs.
+	("wstat prohibited", target::EPERM),
+	("directory entry not found", target::ENOENT),
+	("file not found", target::ENOENT),
+	("fid unknown or out of range", target::EBADF),
+	("permission denied", target::EACCES),
+	("file does not exist", target::ENOENT),
+	("authentication failed", target::ECONNREFUSED),
+	("bad offset in directory read", target::ESPIPE),
+	("bad use of fid", target::EBADF),
+	("wstat can't convert between files and directories", target::EPERM),
+	("directory is not empty", target::ENOTEMPTY),
+	("file exists", target::EEXIST),
+	("file already exists", target::EEXIST),
+	("file or directory already exists", target::EEXIST),
+	("fid already in use", target::EBADF),
+	("file in use", target::ETXTBSY),
+	("i/o error", target::EIO),
+	("file already open for I/O", target::ETXTBSY),
+	("illegal mode", target::EINVAL),
+	("illegal name", target::ENAMETOOLONG),
+	("not a directory", target::ENOTDIR),
+	("not a member of proposed group", target::EPERM),
+	("not owner", target::EACCES),
+	("only owner can change group in wstat", target::EACCES),
+	("read only file system", target::EROFS),
+	("no access to special file", target::EPERM),
+	("i/o count too large", target::EIO),
+	("unknown group", target::EINVAL),
+	("unknown user", target::EINVAL),
+	("bogus wstat buffer", target::EPROTO),
+	("exclusive use file already open", target::EAGAIN),
+	("corrupted directory entry", target::EIO),
+	("corrupted file entry", target::EIO),
+	("corrupted block label", target::EIO),
+	("corrupted meta data", target::EIO),
+	("illegal offset", target::EINVAL),
+	("illegal path element", target::ENOENT),
+	("root of file system is corrupted", target::EIO),
+	("corrupted super block", target::EIO),
+	("protocol botch", target::EPROTO),
+	("file system is full", target::ENOSPC),
+	("file is in use", target::EAGAIN),
+	("directory entry is not allocated", target::ENOENT),
+	("file is read only", target::EROFS),
+	("file has been removed", target::EIDRM),
+	("only support truncation to zero length", target::EPERM),
+	("cannot remove root", target::EPERM),
+	("file too big", target::EFBIG),
+	("venti i/o error", target::EIO),
+];
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kernel;
+
+	fn err(name: &str) -> Error {
+		Arch::TARGET.err_from_name(name).unwrap()
+	}
+
+	#[test]
+	fn round_trip() {
+		let msg = Rlerror::new(0x1234, err("ENOENT"), Arch::Generic).unwrap();
+		assert_eq!((msg.tag, msg.ecode), (0x1234, 2));
+		let buf = msg.encode();
+		assert_eq!(buf, [11, 0, 0, 0, RLERROR, 0x34, 0x12, 2, 0, 0, 0]);
+		assert_eq!(Rlerror::decode(&buf), Some(msg));
+		assert_eq!(msg.error(Arch::Generic), Some(err("ENOENT")));
+
+		// Bytes after the end of the message are ignored.
+		let mut longer = [0xFF; 16];
+		longer[..Rlerror::SIZE].copy_from_slice(&buf);
+		assert_eq!(Rlerror::decode(&longer), Some(msg));
+	}
+
+	#[test]
+	fn decode_invalid() {
+		let buf = Rlerror { tag: 1, ecode: 2 }.encode();
+		for len in 0..Rlerror::SIZE {
+			assert_eq!(Rlerror::decode(&buf[..len]), None);
+		}
+
+		// Rerror, the 9P2000.u error message.
+		let mut wrong_type = buf;
+		wrong_type[4] = 107;
+		assert_eq!(Rlerror::decode(&wrong_type), None);
+
+		let mut wrong_size = buf;
+		wrong_size[0] = 12;
+		assert_eq!(Rlerror::decode(&wrong_size), None);
+		let mut wrong_size = buf;
+		wrong_size[3] = 1;
+		assert_eq!(Rlerror::decode(&wrong_size), None);
+	}
+
+	#[test]
+	fn translate() {
+		let mips = Error::new(1133).unwrap();
+		let x86 = Error::new(122).unwrap();
+		assert_eq!(Arch::Mips.translate(mips, Arch::X86), Some(x86));
+		assert_eq!(Arch::X86.translate(x86, Arch::Mips), Some(mips));
+
+		// Aliases translate to the error number of the same name.
+		let deadlock = Arch::Powerpc.err_from_name("EDEADLOCK").unwrap();
+		let sparc = Arch::Sparc.err_from_name("EDEADLOCK");
+		assert_eq!(Arch::Powerpc.translate(deadlock, Arch::Sparc), sparc);
+
+		// EDQUOT is 1133 on mips, which is undefined on x86.
+		assert_eq!(Arch::X86.translate(mips, Arch::Mips), None);
+		let undefined = Error::new(4000).unwrap();
+		assert_eq!(Arch::Mips.translate(undefined, Arch::X86), None);
+	}
+
+	#[test]
+	fn rlerror_translate() {
+		let edquot = err("EDQUOT");
+		let msg = Rlerror::new(1, edquot, Arch::Mips).unwrap();
+		assert_eq!(msg.ecode, 1133);
+		assert_eq!(msg.error(Arch::Mips), Some(edquot));
+		let msg = Rlerror::new(1, edquot, Arch::X86).unwrap();
+		assert_eq!(msg.ecode, 122);
+		assert_eq!(msg.error(Arch::X86), Some(edquot));
+
+		for &ecode in [0, 0x1_0000 + 2].iter() {
+			let msg = Rlerror { tag: 1, ecode };
+			assert_eq!(msg.error(Arch::X86), None);
+		}
+	}
+
+	#[test]
+	fn kernel_errors() {
+		let errors = [
+			(kernel::ERESTARTSYS, 512),
+			(kernel::EJUKEBOX, 528),
+			(kernel::ENOGRACE, 531),
+		];
+		for &(err, ecode) in errors.iter() {
+			for &wire in Arch::ALL.iter() {
+				let msg = Rlerror::new(1, err, wire).unwrap();
+				assert_eq!(msg.ecode, ecode);
+				assert_eq!(msg.error(wire), Some(err));
+				assert_eq!(Arch::TARGET.translate(err, wire), Some(err));
+			}
+		}
+		// 520 is not used by the kernel.
+		let unused = Error::new(520).unwrap();
+		assert_eq!(Arch::Mips.translate(unused, Arch::X86), None);
+	}
+
+	#[test]
+	fn enames() {
+		assert_eq!(from_ename("Bad file descriptor"), Some(target::EBADF));
+		assert_eq!(
+			from_ename("Resource temporarily unavailable"),
+			Some(target::EAGAIN),
+		);
+		assert_eq!(from_ename("Cannot allocate memory"), Some(target::ENOMEM));
+		assert_eq!(
+			from_ename("Inappropriate ioctl for device"),
+			Some(target::ENOTTY),
+		);
+		assert_eq!(from_ename("Disk quota exceeded"), Some(target::EDQUOT));
+		assert_eq!(from_ename("file does not exist"), Some(target::ENOENT));
+		assert_eq!(from_ename("not owner"), Some(target::EACCES));
+		assert_eq!(from_ename("BAD FILE DESCRIPTOR"), Some(target::EBADF));
+
+		// Descriptions from the kernel headers, and names.
+		assert_eq!(from_ename("Bad file number"), Some(err("EBADF")));
+		assert_eq!(from_ename("Try again"), Some(err("EAGAIN")));
+		assert_eq!(from_ename("Not a typewriter"), Some(err("ENOTTY")));
+		assert_eq!(from_ename("ENOENT"), Some(err("ENOENT")));
+
+		assert_eq!(from_ename(""), None);
+		assert_eq!(from_ename("no such thing"), None);
+		assert_eq!(
+			from_ename("u9fs authnone: no authentication required"),
+			None,
+		);
+	}
+
+	#[test]
+	fn enames_table() {
+		for &(ename, err) in ENAMES.iter() {
+			assert_eq!(from_ename(ename), Some(err), "{:?}", ename);
+			assert!(target::ERRNOS.iter().any(|e| e.error == err));
+		}
+	}
+}
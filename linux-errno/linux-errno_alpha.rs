@@ -301,8 +301,9 @@ errno_constants! {
 	EHWPOISON = 139,
 }
 
-/// Alias for [EDEADLK]
-pub const EDEADLOCK: crate::Error = EDEADLK;
-
-/// Operation would block (alias for [EAGAIN])
-pub const EWOULDBLOCK: crate::Error = EAGAIN;
+errno_aliases! {
+	/// Alias for [EDEADLK]
+	EDEADLOCK = EDEADLK,
+	/// Operation would block (alias for [EAGAIN])
+	EWOULDBLOCK = EAGAIN,
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::arch::Arch;
+use crate::{ErrnoName, Error};
+
+/// Generates error numbers uniformly over the valid range `[1, 4096)`.
+impl<'a> Arbitrary<'a> for Error {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Error> {
+		let errno = u.int_in_range(1..=0xFFF)?;
+		Ok(unsafe { Error::new_unchecked(errno) })
+	}
+
+	#[inline]
+	fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+		(2, Some(2))
+	}
+}
+
+impl<'a> Arbitrary<'a> for Arch {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Arch> {
+		u.choose(&Arch::ALL).copied()
+	}
+
+	#[inline]
+	fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+		(1, Some(1))
+	}
+}
+
+impl<'a> Arbitrary<'a> for ErrnoName {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<ErrnoName> {
+		u.choose(ErrnoName::ALL).copied()
+	}
+
+	#[inline]
+	fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+		(1, Some(1))
+	}
+}
+
+impl Arch {
+	/// Generates an error number from fuzzer input, weighted toward the
+	/// error numbers defined on this architecture.
+	///
+	/// Seven in eight generated errors are defined on this architecture, with
+	/// each defined error number equally likely (aliases such as `EWOULDBLOCK`
+	/// are not counted separately). The rest are uniform over the valid range
+	/// `[1, 4096)`, so that code under test also sees unknown error numbers.
+	///
+	/// ```
+	/// # use linux_errno::arch::Arch;
+	/// use arbitrary::Unstructured;
+	///
+	/// let mut u = Unstructured::new(b"fuzzer input");
+	/// let err = Arch::Mips.arbitrary_error(&mut u).unwrap();
+	/// assert!(err.get() < 4096);
+	/// ```
+	pub fn arbitrary_error(self, u: &mut Unstructured<'_>) -> Result<Error> {
+		if u.ratio(1u8, 8u8)? {
+			return Error::arbitrary(u);
+		}
+		Ok(u.choose(self.errnos())?.error)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+
+	use super::*;
+
+	// Deterministic pseudo-random fuzzer input.
+	fn input(len: usize) -> Vec<u8> {
+		let mut state = 0x2545_F491_4F6C_DD1Du64;
+		(0..len)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 7;
+				state ^= state << 17;
+				state as u8
+			})
+			.collect()
+	}
+
+	#[test]
+	fn error() {
+		let data = input(4096);
+		let mut u = Unstructured::new(&data);
+		while !u.is_empty() {
+			let err = Error::arbitrary(&mut u).unwrap();
+			assert!(err.get() >= 1 && err.get() <= 0xFFF);
+		}
+		let mut u = Unstructured::new(&[]);
+		assert_eq!(Error::arbitrary(&mut u).map(|e| e.get()), Ok(1));
+	}
+
+	#[test]
+	fn arch_error() {
+		let data = input(1 << 16);
+		for &arch in Arch::ALL.iter() {
+			let mut u = Unstructured::new(&data);
+			let mut known = 0;
+			let mut total = 0;
+			while u.len() >= 4 {
+				let err = arch.arbitrary_error(&mut u).unwrap();
+				total += 1;
+				if arch.err_name(err).is_some() {
+					known += 1;
+				}
+			}
+			// Seven in eight are known, plus the few uniform errors that
+			// happen to be defined.
+			assert!(known * 4 > total * 3, "{:?}: {}/{}", arch, known, total);
+		}
+	}
+
+	#[test]
+	fn errnos() {
+		for &arch in Arch::ALL.iter() {
+			let errnos = arch.errnos();
+			for (i, entry) in errnos.iter().enumerate() {
+				let rest = &errnos[i + 1..];
+				let dup = rest.iter().any(|e| e.error == entry.error);
+				assert!(!dup, "{} {:?}", entry.name, arch);
+			}
+			for entry in arch.entries() {
+				assert!(errnos.iter().any(|e| e.error == entry.error));
+			}
+		}
+	}
+}
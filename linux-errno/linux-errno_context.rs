@@ -0,0 +1,342 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use core::fmt::{self, Write};
+
+use crate::{target_err_message, target_err_name, Error};
+
+/// An error number with information about the system call that returned it.
+///
+/// An `ErrorContext` records the name of the system call, and optionally
+/// the file descriptor and path it was called with. The path is copied into
+/// a fixed-size buffer, so `ErrorContext` can be used without an allocator.
+///
+/// ```
+/// # use linux_errno::{ErrorContext, ENOENT};
+/// let err = ErrorContext::new("openat", ENOENT).with_path(b"/etc/foo");
+/// assert_eq!(
+///     format!("{}", err),
+///     "openat(\"/etc/foo\"): ENOENT (No such file or directory)",
+/// );
+/// assert_eq!(err, ENOENT);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ErrorContext {
+	error: Error,
+	syscall: &'static str,
+	fd: Option<i32>,
+	path_len: Option<u8>,
+	path_truncated: bool,
+	path: [u8; ErrorContext::PATH_CAPACITY],
+}
+
+impl ErrorContext {
+	/// The maximum length of a path stored in an `ErrorContext`. Longer
+	/// paths are truncated.
+	pub const PATH_CAPACITY: usize = 128;
+
+	/// Creates a new `ErrorContext` for an error returned by `syscall`.
+	pub const fn new(syscall: &'static str, error: Error) -> ErrorContext {
+		ErrorContext {
+			error,
+			syscall,
+			fd: None,
+			path_len: None,
+			path_truncated: false,
+			path: [0; ErrorContext::PATH_CAPACITY],
+		}
+	}
+
+	/// Sets the file descriptor that the system call was called with.
+	pub const fn with_fd(mut self, fd: i32) -> ErrorContext {
+		self.fd = Some(fd);
+		self
+	}
+
+	/// Sets the path that the system call was called with. Paths longer
+	/// than [`PATH_CAPACITY`](ErrorContext::PATH_CAPACITY) are truncated.
+	pub fn with_path(mut self, path: &[u8]) -> ErrorContext {
+		let len = path.len().min(ErrorContext::PATH_CAPACITY);
+		self.path[..len].copy_from_slice(&path[..len]);
+		self.path_len = Some(len as u8);
+		self.path_truncated = len < path.len();
+		self
+	}
+
+	/// Returns the error number.
+	pub const fn error(&self) -> Error {
+		self.error
+	}
+
+	/// Returns the name of the system call.
+	pub const fn syscall(&self) -> &'static str {
+		self.syscall
+	}
+
+	/// Returns the file descriptor that the system call was called with.
+	pub const fn fd(&self) -> Option<i32> {
+		self.fd
+	}
+
+	/// Returns the path that the system call was called with. If the path
+	/// was truncated, only the stored prefix is returned.
+	pub fn path(&self) -> Option<&[u8]> {
+		let len = self.path_len?;
+		Some(&self.path[..usize::from(len)])
+	}
+
+	/// Returns `true` if the path was longer than
+	/// [`PATH_CAPACITY`](ErrorContext::PATH_CAPACITY) and was truncated.
+	pub const fn is_path_truncated(&self) -> bool {
+		self.path_truncated
+	}
+}
+
+impl fmt::Debug for ErrorContext {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut s = f.debug_struct("ErrorContext");
+		s.field("error", &self.error);
+		s.field("syscall", &self.syscall);
+		s.field("fd", &self.fd);
+		s.field("path", &self.path().map(|path| QuotedPath {
+			path,
+			truncated: self.path_truncated,
+		}));
+		s.finish()
+	}
+}
+
+impl fmt::Display for ErrorContext {
+	/// Formats the error in the style of `strace`, for example
+	/// `openat("/etc/foo"): ENOENT (No such file or directory)`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.syscall)?;
+		f.write_char('(')?;
+		if let Some(fd) = self.fd {
+			write!(f, "{}", fd)?;
+			if self.path_len.is_some() {
+				f.write_str(", ")?;
+			}
+		}
+		if let Some(path) = self.path() {
+			let truncated = self.path_truncated;
+			write!(f, "{:?}", QuotedPath { path, truncated })?;
+		}
+		f.write_str("): ")?;
+		match target_err_name(self.error) {
+			Some(name) => f.write_str(name)?,
+			None => write!(f, "{}", self.error.get())?,
+		}
+		if let Some(message) = target_err_message(self.error) {
+			write!(f, " ({})", message)?;
+		}
+		Ok(())
+	}
+}
+
+// The `Display` output already includes the error number, so it is not also
+// reported as the `source()`.
+#[cfg(feature = "core-error")]
+impl core::error::Error for ErrorContext {}
+
+#[cfg(all(feature = "std", not(feature = "core-error")))]
+impl std::error::Error for ErrorContext {}
+
+impl PartialEq for ErrorContext {
+	fn eq(&self, other: &ErrorContext) -> bool {
+		self.error == other.error
+			&& self.syscall == other.syscall
+			&& self.fd == other.fd
+			&& self.path() == other.path()
+			&& self.path_truncated == other.path_truncated
+	}
+}
+
+impl Eq for ErrorContext {}
+
+impl PartialEq<Error> for ErrorContext {
+	#[inline]
+	fn eq(&self, other: &Error) -> bool {
+		self.error == *other
+	}
+}
+
+impl PartialEq<ErrorContext> for Error {
+	#[inline]
+	fn eq(&self, other: &ErrorContext) -> bool {
+		*self == other.error
+	}
+}
+
+impl From<ErrorContext> for Error {
+	#[inline]
+	fn from(ctx: ErrorContext) -> Error {
+		ctx.error
+	}
+}
+
+// A path formatted as a quoted string, with invalid UTF-8 escaped as bytes
+// and truncation marked by a trailing `...`.
+struct QuotedPath<'a> {
+	path: &'a [u8],
+	truncated: bool,
+}
+
+impl fmt::Debug for QuotedPath<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_char('"')?;
+		let mut rest = self.path;
+		loop {
+			match core::str::from_utf8(rest) {
+				Ok(valid) => {
+					write_escaped(f, valid)?;
+					break;
+				},
+				Err(err) => {
+					let (valid, invalid) = rest.split_at(err.valid_up_to());
+					let invalid_len = err.error_len().unwrap_or(invalid.len());
+					// SAFETY: `from_utf8` checked the bytes before `invalid`.
+					write_escaped(f, unsafe {
+						core::str::from_utf8_unchecked(valid)
+					})?;
+					for byte in &invalid[..invalid_len] {
+						write!(f, "\\x{:02x}", byte)?;
+					}
+					rest = &invalid[invalid_len..];
+				},
+			}
+		}
+		f.write_char('"')?;
+		if self.truncated {
+			f.write_str("...")?;
+		}
+		Ok(())
+	}
+}
+
+fn write_escaped(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+	for c in s.chars() {
+		for e in c.escape_debug() {
+			f.write_char(e)?;
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::format;
+
+	use super::*;
+	use crate::target;
+
+	#[test]
+	fn display() {
+		let err =
+			ErrorContext::new("openat", target::ENOENT).with_path(b"/etc/foo");
+		assert_eq!(
+			format!("{}", err),
+			"openat(\"/etc/foo\"): ENOENT (No such file or directory)",
+		);
+
+		let err = ErrorContext::new("close", target::EBADF).with_fd(3);
+		assert_eq!(format!("{}", err), "close(3): EBADF (Bad file number)");
+
+		let err = ErrorContext::new("openat", target::EACCES)
+			.with_fd(-100)
+			.with_path(b"foo");
+		assert_eq!(
+			format!("{}", err),
+			"openat(-100, \"foo\"): EACCES (Permission denied)",
+		);
+
+		let err = ErrorContext::new("sync", Error::new(4000).unwrap());
+		assert_eq!(format!("{}", err), "sync(): 4000");
+	}
+
+	#[test]
+	fn path_escapes() {
+		let err = ErrorContext::new("mkdir", target::EEXIST)
+			.with_path(b"a\"b\n\xFFc\xE2\x82");
+		assert_eq!(
+			format!("{}", err),
+			"mkdir(\"a\\\"b\\n\\xffc\\xe2\\x82\"): EEXIST (File exists)",
+		);
+	}
+
+	#[test]
+	fn path_truncated() {
+		let path = [b'a'; ErrorContext::PATH_CAPACITY + 1];
+		let err = ErrorContext::new("stat", target::ENOENT).with_path(&path);
+		assert!(err.is_path_truncated());
+		assert_eq!(err.path(), Some(&path[..ErrorContext::PATH_CAPACITY]));
+		assert!(format!("{}", err).starts_with("stat(\"aaaa"));
+		assert!(format!("{}", err).contains("aaa\"...): ENOENT"));
+
+		let err = err.with_path(&path[..ErrorContext::PATH_CAPACITY]);
+		assert!(!err.is_path_truncated());
+		assert_eq!(err.path(), Some(&path[..ErrorContext::PATH_CAPACITY]));
+	}
+
+	#[test]
+	fn accessors() {
+		let err = ErrorContext::new("read", target::EINTR);
+		assert_eq!(err.error(), target::EINTR);
+		assert_eq!(err.syscall(), "read");
+		assert_eq!(err.fd(), None);
+		assert_eq!(err.path(), None);
+		assert_eq!(err.with_path(b"").path(), Some(&b""[..]));
+	}
+
+	#[test]
+	fn equality() {
+		let err = ErrorContext::new("read", target::EINTR).with_fd(0);
+		assert_eq!(err, target::EINTR);
+		assert_eq!(target::EINTR, err);
+		assert_ne!(err, target::EAGAIN);
+		assert_eq!(Error::from(err), target::EINTR);
+
+		assert_eq!(err, ErrorContext::new("read", target::EINTR).with_fd(0));
+		assert_ne!(err, ErrorContext::new("read", target::EINTR).with_fd(1));
+		assert_ne!(err, ErrorContext::new("write", target::EINTR).with_fd(0));
+		assert_ne!(err, err.with_path(b"foo"));
+	}
+
+	#[test]
+	fn debug() {
+		let err = ErrorContext::new("close", target::EBADF).with_fd(3);
+		assert_eq!(
+			format!("{:?}", err),
+			"ErrorContext { error: EBADF, syscall: \"close\", fd: Some(3), \
+			 path: None }",
+		);
+		let err = ErrorContext::new("unlink", target::ENOENT).with_path(b"x");
+		assert_eq!(
+			format!("{:?}", err),
+			"ErrorContext { error: ENOENT, syscall: \"unlink\", fd: None, \
+			 path: Some(\"x\") }",
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn no_source() {
+		use std::error::Error as _;
+
+		let err = ErrorContext::new("close", target::EBADF).with_fd(3);
+		assert!(err.source().is_none());
+		assert!(target::EBADF.source().is_none());
+	}
+}
@@ -0,0 +1,190 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{target_err_name, Error};
+
+const LEN: usize = 0xFFF;
+
+/// A histogram of error numbers, with one atomic counter per error number.
+///
+/// `ErrnoCounters` can be updated concurrently from multiple threads without
+/// locking, and does not allocate. It is intended to be stored in a `static`
+/// and exported periodically to a metrics system.
+///
+/// ```
+/// # use linux_errno::{ErrnoCounters, EAGAIN, ENOENT};
+/// static COUNTERS: ErrnoCounters = ErrnoCounters::new();
+///
+/// COUNTERS.record(ENOENT);
+/// COUNTERS.record(EAGAIN);
+/// COUNTERS.record(ENOENT);
+///
+/// for (name, err, count) in COUNTERS.iter() {
+///     println!("{} ({}): {}", name.unwrap_or("?"), err.get(), count);
+/// }
+/// ```
+///
+/// Counters wrap around on overflow.
+pub struct ErrnoCounters {
+	counts: [AtomicUsize; LEN],
+}
+
+impl ErrnoCounters {
+	/// Creates a new `ErrnoCounters` with all counters set to zero.
+	pub const fn new() -> ErrnoCounters {
+		#[allow(clippy::declare_interior_mutable_const)]
+		const ZERO: AtomicUsize = AtomicUsize::new(0);
+		ErrnoCounters { counts: [ZERO; LEN] }
+	}
+
+	/// Increments the counter for an error number.
+	#[inline]
+	pub fn record(&self, err: Error) {
+		self.counter(err).fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Returns the counter for an error number.
+	#[inline]
+	pub fn get(&self, err: Error) -> usize {
+		self.counter(err).load(Ordering::Relaxed)
+	}
+
+	/// Sets all counters to zero.
+	pub fn reset(&self) {
+		for count in self.counts.iter() {
+			count.store(0, Ordering::Relaxed);
+		}
+	}
+
+	/// Returns an iterator over the non-zero counters, in order of error
+	/// number. Each item contains the name of the error number on the
+	/// current target, the error number, and its count. The names of the
+	/// kernel-internal error numbers in the [`kernel`](crate::kernel) module
+	/// are included, as in the `Debug` output.
+	pub fn iter(
+		&self,
+	) -> impl Iterator<Item = (Option<&'static str>, Error, usize)> + '_ {
+		self.counts.iter().zip(1..).filter_map(|(count, errno)| {
+			let count = count.load(Ordering::Relaxed);
+			if count == 0 {
+				return None;
+			}
+			// SAFETY: `errno` is in the range `[1, 4096)`.
+			let err = unsafe { Error::new_unchecked(errno) };
+			Some((target_err_name(err), err, count))
+		})
+	}
+
+	#[inline]
+	fn counter(&self, err: Error) -> &AtomicUsize {
+		&self.counts[usize::from(err.get() - 1)]
+	}
+}
+
+impl Default for ErrnoCounters {
+	fn default() -> ErrnoCounters {
+		ErrnoCounters::new()
+	}
+}
+
+impl fmt::Debug for ErrnoCounters {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut map = f.debug_map();
+		for (_, err, count) in self.iter() {
+			map.entry(&err, &count);
+		}
+		map.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use std::{format, thread};
+
+	use super::*;
+	use crate::{kernel, target};
+
+	#[test]
+	fn record() {
+		let counters = ErrnoCounters::new();
+		assert_eq!(counters.iter().count(), 0);
+
+		let max = Error::new(0xFFF).unwrap();
+		counters.record(target::ENOENT);
+		counters.record(max);
+		counters.record(target::ENOENT);
+		counters.record(target::EPERM);
+
+		assert_eq!(counters.get(target::ENOENT), 2);
+		assert_eq!(counters.get(target::EPERM), 1);
+		assert_eq!(counters.get(max), 1);
+		assert_eq!(counters.get(target::EINTR), 0);
+
+		let mut iter = counters.iter();
+		assert_eq!(iter.next(), Some((Some("EPERM"), target::EPERM, 1)));
+		assert_eq!(iter.next(), Some((Some("ENOENT"), target::ENOENT, 2)));
+		assert_eq!(iter.next(), Some((None, max, 1)));
+		assert_eq!(iter.next(), None);
+
+		assert_eq!(
+			format!("{:?}", counters),
+			"{EPERM: 1, ENOENT: 2, E?4095: 1}",
+		);
+
+		counters.reset();
+		assert_eq!(counters.iter().count(), 0);
+		assert_eq!(counters.get(target::ENOENT), 0);
+	}
+
+	#[test]
+	fn record_kernel() {
+		let counters = ErrnoCounters::new();
+		counters.record(kernel::ERESTARTSYS);
+		counters.record(kernel::EJUKEBOX);
+		counters.record(kernel::ERESTARTSYS);
+
+		let mut iter = counters.iter();
+		let restart = (Some("ERESTARTSYS"), kernel::ERESTARTSYS, 2);
+		assert_eq!(iter.next(), Some(restart));
+		let jukebox = (Some("EJUKEBOX"), kernel::EJUKEBOX, 1);
+		assert_eq!(iter.next(), Some(jukebox));
+		assert_eq!(iter.next(), None);
+
+		assert_eq!(format!("{:?}", counters), "{ERESTARTSYS: 2, EJUKEBOX: 1}");
+	}
+
+	#[test]
+	fn concurrent() {
+		static COUNTERS: ErrnoCounters = ErrnoCounters::new();
+
+		let threads: Vec<_> = (0..4)
+			.map(|_| {
+				thread::spawn(|| {
+					for _ in 0..1000 {
+						COUNTERS.record(target::EAGAIN);
+					}
+				})
+			})
+			.collect();
+		for thread in threads {
+			thread.join().unwrap();
+		}
+		assert_eq!(COUNTERS.get(target::EAGAIN), 4000);
+	}
+}
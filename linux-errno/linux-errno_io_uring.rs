@@ -0,0 +1,266 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use crate::Error;
+
+// https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/include/uapi/linux/io_uring.h?h=v6.0
+
+const IORING_CQE_F_BUFFER: u32 = 1 << 0;
+const IORING_CQE_F_MORE: u32 = 1 << 1;
+const IORING_CQE_F_SOCK_NONEMPTY: u32 = 1 << 2;
+const IORING_CQE_F_NOTIF: u32 = 1 << 3;
+const IORING_CQE_BUFFER_SHIFT: u32 = 16;
+
+impl Error {
+	/// Decodes the `res` field of an `io_uring` completion queue entry.
+	///
+	/// Values in the range `[-4095, -1]` are negated error numbers, and
+	/// every other value is a successful result. The kernel never produces
+	/// a result below `-4095`, so such values are returned as their
+	/// two's-complement `u32`, matching [`ErrOrUsize`](crate::ErrOrUsize).
+	///
+	/// ```
+	/// # use linux_errno::{Error, ENOENT};
+	/// assert_eq!(Error::from_cqe_res(512), Ok(512));
+	/// assert_eq!(Error::from_cqe_res(-(ENOENT.get() as i32)), Err(ENOENT));
+	/// ```
+	#[inline]
+	pub const fn from_cqe_res(res: i32) -> Result<u32, Error> {
+		match Error::from_negated(res) {
+			Some(err) => Err(err),
+			None => Ok(res as u32),
+		}
+	}
+}
+
+/// The result of an `io_uring` completion queue entry.
+///
+/// A `CqeResult` holds the `res` and `flags` fields of a `struct
+/// io_uring_cqe`. Besides decoding `res`, it interprets the error numbers
+/// to which `io_uring` gives special meaning:
+///
+/// * `ECANCELED`: the request was cancelled, by `IORING_OP_ASYNC_CANCEL`,
+///   by the failure of an earlier request in its link chain, or by the
+///   expiry of a linked timeout.
+/// * `ETIME`: an `IORING_OP_TIMEOUT` or `IORING_OP_LINK_TIMEOUT` expired.
+///   For a timeout request this is the expected completion, not a failure.
+/// * `ENOBUFS`: the provided buffer group of the request was empty. A
+///   multishot request stops, and must be re-armed after buffers are
+///   provided.
+///
+/// The error numbers are those of the current target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CqeResult {
+	/// The `res` field: a result, or a negated error number.
+	pub res: i32,
+	/// The `flags` field, a combination of `IORING_CQE_F_*` flags.
+	pub flags: u32,
+}
+
+impl CqeResult {
+	/// `IORING_CQE_F_BUFFER`: the upper 16 bits of `flags` are a buffer ID.
+	pub const F_BUFFER: u32 = IORING_CQE_F_BUFFER;
+
+	/// `IORING_CQE_F_MORE`: the request will post more completions.
+	pub const F_MORE: u32 = IORING_CQE_F_MORE;
+
+	/// `IORING_CQE_F_SOCK_NONEMPTY`: the socket has more data to read.
+	pub const F_SOCK_NONEMPTY: u32 = IORING_CQE_F_SOCK_NONEMPTY;
+
+	/// `IORING_CQE_F_NOTIF`: this completion is a zero-copy send
+	/// notification.
+	pub const F_NOTIF: u32 = IORING_CQE_F_NOTIF;
+
+	/// Creates a `CqeResult` from the `res` and `flags` fields of a
+	/// completion queue entry.
+	#[inline]
+	pub const fn new(res: i32, flags: u32) -> CqeResult {
+		CqeResult { res, flags }
+	}
+
+	/// Decodes `res` as a result or an error number.
+	///
+	/// See [`Error::from_cqe_res`] for details.
+	#[inline]
+	pub const fn result(&self) -> Result<u32, Error> {
+		Error::from_cqe_res(self.res)
+	}
+
+	/// Returns the error number, or `None` if the request succeeded.
+	#[inline]
+	pub const fn err(&self) -> Option<Error> {
+		match self.result() {
+			Ok(_) => None,
+			Err(err) => Some(err),
+		}
+	}
+
+	/// Returns `true` if the request will post more completions, which is
+	/// the case for multishot requests that have not stopped.
+	#[inline]
+	pub const fn has_more(&self) -> bool {
+		self.flags & IORING_CQE_F_MORE != 0
+	}
+
+	/// Returns the ID of the provided buffer selected by the request, or
+	/// `None` if no buffer was selected.
+	#[inline]
+	pub const fn buffer_id(&self) -> Option<u16> {
+		if self.flags & IORING_CQE_F_BUFFER == 0 {
+			return None;
+		}
+		Some((self.flags >> IORING_CQE_BUFFER_SHIFT) as u16)
+	}
+
+	/// Returns `true` if the request was cancelled (`ECANCELED`).
+	#[inline]
+	pub fn is_canceled(&self) -> bool {
+		self.err() == Some(crate::ECANCELED)
+	}
+
+	/// Returns `true` if a timeout expired (`ETIME`).
+	#[inline]
+	pub fn is_timeout(&self) -> bool {
+		self.err() == Some(crate::ETIME)
+	}
+
+	/// Returns `true` if the provided buffer group of the request was empty
+	/// (`ENOBUFS`).
+	#[inline]
+	pub fn is_out_of_buffers(&self) -> bool {
+		self.err() == Some(crate::ENOBUFS)
+	}
+
+	/// Returns `true` if this is the last completion of a multishot
+	/// request that should be submitted again to keep receiving
+	/// completions.
+	///
+	/// This is only meaningful for the completions of multishot requests.
+	/// A multishot request that stops with `ENOBUFS` must be re-armed after
+	/// buffers are provided, and one that stops after a successful result
+	/// without [`F_MORE`](Self::F_MORE) can be re-armed as is. It does not
+	/// need to be re-armed if it returned `0` (such as end of file), was
+	/// cancelled, or failed with any other error.
+	#[inline]
+	pub fn needs_rearm(&self) -> bool {
+		if self.has_more() {
+			return false;
+		}
+		match self.result() {
+			Ok(n) => n > 0,
+			Err(err) => err == crate::ENOBUFS,
+		}
+	}
+
+	/// Converts this `CqeResult` to a `Result`, treating an expired
+	/// timeout as success.
+	///
+	/// This is the usual interpretation of the completion of an
+	/// `IORING_OP_TIMEOUT` request, which reports `ETIME` when the timeout
+	/// expires and `0` when it completes because enough other requests
+	/// completed.
+	#[inline]
+	pub fn timeout_result(&self) -> Result<u32, Error> {
+		match self.result() {
+			Err(err) if err == crate::ETIME => Ok(0),
+			result => result,
+		}
+	}
+}
+
+impl From<CqeResult> for Result<u32, Error> {
+	#[inline]
+	fn from(cqe: CqeResult) -> Result<u32, Error> {
+		cqe.result()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{ECANCELED, ENOBUFS, ENOENT, ETIME};
+
+	#[test]
+	fn from_cqe_res() {
+		assert_eq!(Error::from_cqe_res(0), Ok(0));
+		assert_eq!(Error::from_cqe_res(i32::MAX), Ok(0x7FFF_FFFF));
+		assert_eq!(Error::from_cqe_res(-1), Err(Error::new(1).unwrap()));
+		let max = Error::new(4095).unwrap();
+		assert_eq!(Error::from_cqe_res(-4095), Err(max));
+		assert_eq!(Error::from_cqe_res(-4096), Ok(0xFFFF_F000));
+		assert_eq!(Error::from_cqe_res(i32::MIN), Ok(0x8000_0000));
+	}
+
+	#[test]
+	fn flags() {
+		let cqe = CqeResult::new(16, 0);
+		assert!(!cqe.has_more());
+		assert_eq!(cqe.buffer_id(), None);
+
+		let flags = CqeResult::F_BUFFER | CqeResult::F_MORE | (0xBEEF << 16);
+		let cqe = CqeResult::new(16, flags);
+		assert!(cqe.has_more());
+		assert_eq!(cqe.buffer_id(), Some(0xBEEF));
+
+		let cqe = CqeResult::new(0, CqeResult::F_BUFFER);
+		assert_eq!(cqe.buffer_id(), Some(0));
+
+		// The buffer ID is ignored without `F_BUFFER`.
+		let cqe = CqeResult::new(16, 0xBEEF << 16);
+		assert_eq!(cqe.buffer_id(), None);
+	}
+
+	#[test]
+	fn errors() {
+		let neg = |err: Error| -(err.get() as i32);
+
+		let cqe = CqeResult::new(8, 0);
+		assert_eq!(cqe.result(), Ok(8));
+		assert_eq!(cqe.err(), None);
+		assert_eq!(Result::from(cqe), Ok(8));
+
+		let cqe = CqeResult::new(neg(ECANCELED), 0);
+		assert_eq!(cqe.err(), Some(ECANCELED));
+		assert!(cqe.is_canceled());
+		assert!(!cqe.is_timeout());
+		assert!(!cqe.is_out_of_buffers());
+
+		let cqe = CqeResult::new(neg(ETIME), 0);
+		assert!(cqe.is_timeout());
+		assert_eq!(cqe.timeout_result(), Ok(0));
+
+		let cqe = CqeResult::new(neg(ENOBUFS), 0);
+		assert!(cqe.is_out_of_buffers());
+
+		let cqe = CqeResult::new(neg(ENOENT), 0);
+		assert_eq!(cqe.timeout_result(), Err(ENOENT));
+		assert!(!cqe.is_canceled());
+	}
+
+	#[test]
+	fn needs_rearm() {
+		let more = CqeResult::F_MORE;
+		let neg = |err: Error| -(err.get() as i32);
+
+		assert!(CqeResult::new(100, 0).needs_rearm());
+		assert!(CqeResult::new(neg(ENOBUFS), 0).needs_rearm());
+
+		assert!(!CqeResult::new(100, more).needs_rearm());
+		assert!(!CqeResult::new(0, 0).needs_rearm());
+		assert!(!CqeResult::new(0, more).needs_rearm());
+		assert!(!CqeResult::new(neg(ECANCELED), 0).needs_rearm());
+		assert!(!CqeResult::new(neg(ENOENT), 0).needs_rearm());
+	}
+}
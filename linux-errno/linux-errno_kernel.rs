@@ -0,0 +1,80 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+#![allow(unused)]
+
+errno_constants! {
+	/// The kernel-internal error numbers, as an enum.
+	///
+	/// Unlike the [`Error`](crate::Error) constants, a `KnownError` can be
+	/// matched exhaustively.
+	enum KnownError;
+
+	// https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/include/linux/errno.h?h=v5.19
+
+	/// Restart system call if the signal handler allows it
+	ERESTARTSYS = 512,
+	/// Restart system call unconditionally
+	ERESTARTNOINTR = 513,
+	/// Restart if no handler
+	ERESTARTNOHAND = 514,
+	/// No ioctl command
+	ENOIOCTLCMD = 515,
+	/// Restart by calling sys_restart_syscall
+	ERESTART_RESTARTBLOCK = 516,
+	/// Driver requests probe retry
+	EPROBE_DEFER = 517,
+	/// Open found a stale dentry
+	EOPENSTALE = 518,
+	/// Parameter not supported
+	ENOPARAM = 519,
+
+	/// Illegal NFS file handle
+	EBADHANDLE = 521,
+	/// Update synchronization mismatch
+	ENOTSYNC = 522,
+	/// Cookie is stale
+	EBADCOOKIE = 523,
+	/// Operation is not supported
+	ENOTSUPP = 524,
+	/// Buffer or request is too small
+	ETOOSMALL = 525,
+	/// An untranslatable error occurred
+	ESERVERFAULT = 526,
+	/// Type not supported by server
+	EBADTYPE = 527,
+	/// Request initiated, but will not complete before timeout
+	EJUKEBOX = 528,
+	/// iocb queued, will get completion event
+	EIOCBQUEUED = 529,
+	/// Conflict with recalled state
+	ERECALLCONFLICT = 530,
+	/// NFS file lock reclaim refused
+	ENOGRACE = 531,
+}
+
+/// Returns the name of a kernel-internal error number, such as
+/// `"ERESTARTSYS"`, or `None` if `err` is not kernel-internal.
+#[inline]
+pub const fn name(err: crate::Error) -> Option<&'static str> {
+	err_name(err)
+}
+
+/// Returns the description of a kernel-internal error number, or `None` if
+/// `err` is not kernel-internal.
+#[inline]
+pub const fn message(err: crate::Error) -> Option<&'static str> {
+	err_message(err)
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use crate::Error;
+
+#[link(name = "c")]
+extern "C" {
+	#[cfg(not(target_os = "android"))]
+	#[link_name = "__errno_location"]
+	fn errno_location() -> *mut i32;
+
+	#[cfg(target_os = "android")]
+	#[link_name = "__errno"]
+	fn errno_location() -> *mut i32;
+}
+
+impl Error {
+	/// Returns the current value of the C library's thread-local `errno`.
+	///
+	/// Returns `None` if `errno` is zero, or if it's outside the permitted
+	/// range for Linux error numbers.
+	///
+	/// The location of `errno` is obtained from the C library, with
+	/// `__errno_location()` (glibc, musl) or `__errno()` (bionic). The
+	/// `libc` feature links the C library into the program.
+	#[inline]
+	pub fn last() -> Option<Error> {
+		// SAFETY: `errno_location()` returns a valid pointer to the
+		// thread-local `errno` of the calling thread.
+		let errno = unsafe { *errno_location() };
+		if errno <= 0 || errno > i32::from(u16::MAX) {
+			return None;
+		}
+		Error::new(errno as u16)
+	}
+
+	/// Sets the C library's thread-local `errno`. Setting it to `None` sets
+	/// `errno` to zero.
+	///
+	/// See [`Error::last`] for how the location of `errno` is obtained.
+	#[inline]
+	pub fn set_last(err: Option<Error>) {
+		let errno = match err {
+			Some(err) => i32::from(err.get()),
+			None => 0,
+		};
+		// SAFETY: `errno_location()` returns a valid pointer to the
+		// thread-local `errno` of the calling thread.
+		unsafe { *errno_location() = errno };
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::thread;
+
+	use super::*;
+	use crate::target;
+
+	#[test]
+	fn set_last() {
+		Error::set_last(Some(target::EINVAL));
+		assert_eq!(Error::last(), Some(target::EINVAL));
+		Error::set_last(Some(target::ENOENT));
+		assert_eq!(Error::last(), Some(target::ENOENT));
+		Error::set_last(None);
+		assert_eq!(Error::last(), None);
+	}
+
+	#[test]
+	fn out_of_range() {
+		for &errno in [-1, 0x1000, i32::MAX, i32::MIN].iter() {
+			unsafe { *errno_location() = errno };
+			assert_eq!(Error::last(), None, "{}", errno);
+		}
+		unsafe { *errno_location() = 0xFFF };
+		assert_eq!(Error::last(), Error::new(0xFFF));
+	}
+
+	#[test]
+	fn thread_local() {
+		Error::set_last(Some(target::EINTR));
+		thread::spawn(|| {
+			Error::set_last(Some(target::EAGAIN));
+			assert_eq!(Error::last(), Some(target::EAGAIN));
+		})
+		.join()
+		.unwrap();
+		assert_eq!(Error::last(), Some(target::EINTR));
+	}
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use crate::{target_err_message, target_err_name, Error};
+
+/// Output formats of [`Error::write_to_format`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MessageFormat {
+	/// The name, number, and description, such as
+	/// `"ENOENT (2): No such file or directory"`.
+	Full,
+	/// The name, such as `"ENOENT"`.
+	Name,
+	/// The number in decimal, such as `"2"`.
+	Number,
+	/// The description, such as `"No such file or directory"`. This is the
+	/// format of the `strerror_r()` function in C.
+	Strerror,
+}
+
+impl Error {
+	/// Writes a description of this error to a buffer, in the
+	/// [`MessageFormat::Full`] format, and returns the number of bytes
+	/// written.
+	///
+	/// See [`write_to_format`](Error::write_to_format) for details.
+	#[inline]
+	pub fn write_to(&self, buf: &mut [u8]) -> usize {
+		self.write_to_format(buf, MessageFormat::Full)
+	}
+
+	/// Writes a description of this error to a buffer, and returns the
+	/// number of bytes written.
+	///
+	/// If the buffer is too small, the output is truncated. A buffer of 128
+	/// bytes is large enough for any error number in any format.
+	///
+	/// Names and descriptions are those of the current target, or of the
+	/// kernel-internal error numbers in [`kernel`](crate::kernel). Other
+	/// error numbers are written as `"Unknown error 1234"` in the
+	/// [`Full`](MessageFormat::Full) and [`Strerror`](MessageFormat::Strerror)
+	/// formats, and as their number in the [`Name`](MessageFormat::Name)
+	/// format.
+	///
+	/// This function does not allocate or use `core::fmt`, so it can be
+	/// called from signal handlers and panic handlers.
+	///
+	/// ```
+	/// # use linux_errno::{MessageFormat, ENOENT};
+	/// let mut buf = [0u8; 128];
+	/// let len = ENOENT.write_to(&mut buf);
+	/// assert_eq!(&buf[..len], b"ENOENT (2): No such file or directory");
+	///
+	/// let len = ENOENT.write_to_format(&mut buf, MessageFormat::Strerror);
+	/// assert_eq!(&buf[..len], b"No such file or directory");
+	/// ```
+	pub fn write_to_format(
+		&self,
+		buf: &mut [u8],
+		format: MessageFormat,
+	) -> usize {
+		let mut number = [0u8; 4];
+		let number = format_number(self.get(), &mut number);
+		let name = target_err_name(*self);
+		let message = target_err_message(*self);
+
+		let mut w = Writer { buf, len: 0 };
+		match (format, name, message) {
+			(MessageFormat::Full, Some(name), Some(message)) => {
+				w.write(name.as_bytes());
+				w.write(b" (");
+				w.write(number);
+				w.write(b"): ");
+				w.write(message.as_bytes());
+			},
+			(MessageFormat::Name, Some(name), _) => w.write(name.as_bytes()),
+			(MessageFormat::Strerror, _, Some(message)) => {
+				w.write(message.as_bytes())
+			},
+			(MessageFormat::Name, None, _) | (MessageFormat::Number, _, _) => {
+				w.write(number)
+			},
+			(MessageFormat::Full, _, _) | (MessageFormat::Strerror, _, _) => {
+				w.write(b"Unknown error ");
+				w.write(number);
+			},
+		}
+		w.len
+	}
+}
+
+struct Writer<'a> {
+	buf: &'a mut [u8],
+	len: usize,
+}
+
+impl Writer<'_> {
+	fn write(&mut self, bytes: &[u8]) {
+		let avail = self.buf.len() - self.len;
+		let count = bytes.len().min(avail);
+		self.buf[self.len..self.len + count].copy_from_slice(&bytes[..count]);
+		self.len += count;
+	}
+}
+
+fn format_number(mut n: u16, buf: &mut [u8; 4]) -> &[u8] {
+	let mut start = buf.len();
+	loop {
+		start -= 1;
+		buf[start] = b'0' + (n % 10) as u8;
+		n /= 10;
+		if n == 0 {
+			return &buf[start..];
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{kernel, target};
+
+	const FORMATS: [MessageFormat; 4] = [
+		MessageFormat::Full,
+		MessageFormat::Name,
+		MessageFormat::Number,
+		MessageFormat::Strerror,
+	];
+
+	fn write(err: Error, format: MessageFormat) -> ([u8; 256], usize) {
+		let mut buf = [0u8; 256];
+		let len = err.write_to_format(&mut buf, format);
+		(buf, len)
+	}
+
+	fn check(err: Error, format: MessageFormat, expect: &str) {
+		let (buf, len) = write(err, format);
+		let output = core::str::from_utf8(&buf[..len]).unwrap();
+		assert_eq!(output, expect, "{:?}", format);
+	}
+
+	#[test]
+	fn formats() {
+		let err = target::ENOENT;
+		let full = "ENOENT (2): No such file or directory";
+		check(err, MessageFormat::Full, full);
+		check(err, MessageFormat::Name, "ENOENT");
+		check(err, MessageFormat::Number, "2");
+		check(err, MessageFormat::Strerror, "No such file or directory");
+
+		let mut buf = [0u8; 128];
+		let len = err.write_to(&mut buf);
+		assert_eq!(&buf[..len], b"ENOENT (2): No such file or directory");
+	}
+
+	#[test]
+	fn kernel_internal() {
+		let err = kernel::ERESTARTSYS;
+		check(
+			err,
+			MessageFormat::Full,
+			"ERESTARTSYS (512): Restart system call if the signal handler \
+			 allows it",
+		);
+		check(err, MessageFormat::Name, "ERESTARTSYS");
+	}
+
+	#[test]
+	fn unknown() {
+		let err = Error::new(4000).unwrap();
+		check(err, MessageFormat::Full, "Unknown error 4000");
+		check(err, MessageFormat::Name, "4000");
+		check(err, MessageFormat::Number, "4000");
+		check(err, MessageFormat::Strerror, "Unknown error 4000");
+	}
+
+	#[test]
+	fn numbers() {
+		for &errno in [1, 9, 10, 99, 100, 999, 1000, 4095].iter() {
+			let mut buf = [0u8; 4];
+			let digits = format_number(errno, &mut buf);
+			let parsed = core::str::from_utf8(digits).unwrap().parse();
+			assert_eq!(parsed, Ok(errno));
+		}
+	}
+
+	#[test]
+	fn truncated() {
+		for &format in FORMATS.iter() {
+			let (full, full_len) = write(target::ENOENT, format);
+			for len in 0..full_len {
+				let mut buf = [0xAAu8; 256];
+				let written =
+					target::ENOENT.write_to_format(&mut buf[..len], format);
+				assert_eq!(written, len, "{:?}", format);
+				assert_eq!(buf[..len], full[..len], "{:?}", format);
+				assert!(buf[len..].iter().all(|&b| b == 0xAA), "{:?}", format);
+			}
+		}
+	}
+
+	#[test]
+	fn fits_in_128_bytes() {
+		for err in (1..0x1000).filter_map(Error::new) {
+			for &format in FORMATS.iter() {
+				let (buf, len) = write(err, format);
+				assert!(len <= 128, "{:?} {:?}", err, format);
+				assert!(core::str::from_utf8(&buf[..len]).is_ok());
+			}
+		}
+	}
+}
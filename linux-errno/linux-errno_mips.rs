@@ -294,5 +294,7 @@ errno_constants! {
 	EDQUOT = 1133,
 }
 
-/// Operation would block (alias for [EAGAIN])
-pub const EWOULDBLOCK: crate::Error = EAGAIN;
+errno_aliases! {
+	/// Operation would block (alias for [EAGAIN])
+	EWOULDBLOCK = EAGAIN,
+}
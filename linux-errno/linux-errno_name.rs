@@ -0,0 +1,469 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use crate::Error;
+use crate::arch::Arch;
+
+macro_rules! errno_names {
+	( $( #[doc = $doc:literal] $name:ident = $id:literal , )+ ) => {
+		/// Names of Linux error numbers, independent of architecture.
+		///
+		/// `ErrnoName` contains every name defined on any architecture,
+		/// including aliases such as `EWOULDBLOCK` and architecture-specific
+		/// names such as `ECANCELLED` (`parisc`) or `EPROCLIM` (`sparc`). It
+		/// can be used as a stable identifier for error numbers in wire
+		/// formats and databases, where the numbering of any particular
+		/// architecture would be ambiguous.
+		///
+		/// The numeric value of each name is its error number in
+		/// `asm-generic` (see [`Arch::Generic`]). Aliases and names that are
+		/// not defined in `asm-generic` are numbered from 1000, so they never
+		/// collide with an `asm-generic` error number.
+		///
+		/// This numbering is frozen: names will not be renumbered or removed,
+		/// and new names will be added with new values.
+		#[allow(clippy::upper_case_acronyms, non_camel_case_types)]
+		#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+		#[repr(u16)]
+		pub enum ErrnoName {
+		$(
+			#[doc = $doc]
+			$name = $id,
+		)*
+		}
+
+		impl ErrnoName {
+			/// All error names, in order of their numeric values.
+			pub const ALL: &'static [ErrnoName] = &[
+			$(
+				ErrnoName::$name,
+			)*
+			];
+
+			/// Returns the name as a string, such as `"ENOENT"`.
+			pub const fn as_str(self) -> &'static str {
+				match self {
+				$(
+					ErrnoName::$name => stringify!($name),
+				)*
+				}
+			}
+
+			/// Returns the `ErrnoName` for a string, such as `"ENOENT"`.
+			pub fn from_name(name: &str) -> Option<ErrnoName> {
+				match name {
+				$(
+					stringify!($name) => Some(ErrnoName::$name),
+				)*
+					_ => None,
+				}
+			}
+
+			/// Returns the `ErrnoName` with the given numeric value.
+			pub const fn from_id(id: u16) -> Option<ErrnoName> {
+				match id {
+				$(
+					$id => Some(ErrnoName::$name),
+				)*
+					_ => None,
+				}
+			}
+		}
+	}
+}
+
+impl ErrnoName {
+	/// Returns the error number with this name on an architecture, or
+	/// `None` if the name is not defined on that architecture.
+	///
+	/// ```
+	/// # use linux_errno::ErrnoName;
+	/// # use linux_errno::arch::Arch;
+	/// let enomsg = ErrnoName::ENOMSG;
+	/// assert_eq!(enomsg.to_error(Arch::X86).map(|e| e.get()), Some(42));
+	/// assert_eq!(enomsg.to_error(Arch::Mips).map(|e| e.get()), Some(35));
+	/// assert_eq!(ErrnoName::EPROCLIM.to_error(Arch::X86), None);
+	/// ```
+	pub fn to_error(self, arch: Arch) -> Option<Error> {
+		arch.err_from_name(self.as_str())
+	}
+}
+
+impl Error {
+	/// Returns the name of this error number on an architecture, or `None`
+	/// if the error number is not defined on that architecture.
+	///
+	/// For error numbers with aliases, the primary name is returned (for
+	/// example `EAGAIN` instead of `EWOULDBLOCK`).
+	pub fn name_id(self, arch: Arch) -> Option<ErrnoName> {
+		ErrnoName::from_name(arch.err_name(self)?)
+	}
+}
+
+errno_names! {
+	/// Operation not permitted
+	EPERM = 1,
+	/// No such file or directory
+	ENOENT = 2,
+	/// No such process
+	ESRCH = 3,
+	/// Interrupted system call
+	EINTR = 4,
+	/// I/O error
+	EIO = 5,
+	/// No such device or address
+	ENXIO = 6,
+	/// Argument list too long
+	E2BIG = 7,
+	/// Exec format error
+	ENOEXEC = 8,
+	/// Bad file number
+	EBADF = 9,
+	/// No child processes
+	ECHILD = 10,
+	/// Try again
+	EAGAIN = 11,
+	/// Out of memory
+	ENOMEM = 12,
+	/// Permission denied
+	EACCES = 13,
+	/// Bad address
+	EFAULT = 14,
+	/// Block device required
+	ENOTBLK = 15,
+	/// Device or resource busy
+	EBUSY = 16,
+	/// File exists
+	EEXIST = 17,
+	/// Cross-device link
+	EXDEV = 18,
+	/// No such device
+	ENODEV = 19,
+	/// Not a directory
+	ENOTDIR = 20,
+	/// Is a directory
+	EISDIR = 21,
+	/// Invalid argument
+	EINVAL = 22,
+	/// File table overflow
+	ENFILE = 23,
+	/// Too many open files
+	EMFILE = 24,
+	/// Not a typewriter
+	ENOTTY = 25,
+	/// Text file busy
+	ETXTBSY = 26,
+	/// File too large
+	EFBIG = 27,
+	/// No space left on device
+	ENOSPC = 28,
+	/// Illegal seek
+	ESPIPE = 29,
+	/// Read-only file system
+	EROFS = 30,
+	/// Too many links
+	EMLINK = 31,
+	/// Broken pipe
+	EPIPE = 32,
+	/// Math argument out of domain of func
+	EDOM = 33,
+	/// Math result not representable
+	ERANGE = 34,
+	/// Resource deadlock would occur
+	EDEADLK = 35,
+	/// File name too long
+	ENAMETOOLONG = 36,
+	/// No record locks available
+	ENOLCK = 37,
+	/// Invalid system call number
+	ENOSYS = 38,
+	/// Directory not empty
+	ENOTEMPTY = 39,
+	/// Too many symbolic links encountered
+	ELOOP = 40,
+	/// No message of desired type
+	ENOMSG = 42,
+	/// Identifier removed
+	EIDRM = 43,
+	/// Channel number out of range
+	ECHRNG = 44,
+	/// Level 2 not synchronized
+	EL2NSYNC = 45,
+	/// Level 3 halted
+	EL3HLT = 46,
+	/// Level 3 reset
+	EL3RST = 47,
+	/// Link number out of range
+	ELNRNG = 48,
+	/// Protocol driver not attached
+	EUNATCH = 49,
+	/// No CSI structure available
+	ENOCSI = 50,
+	/// Level 2 halted
+	EL2HLT = 51,
+	/// Invalid exchange
+	EBADE = 52,
+	/// Invalid request descriptor
+	EBADR = 53,
+	/// Exchange full
+	EXFULL = 54,
+	/// No anode
+	ENOANO = 55,
+	/// Invalid request code
+	EBADRQC = 56,
+	/// Invalid slot
+	EBADSLT = 57,
+	/// Bad font file format
+	EBFONT = 59,
+	/// Device not a stream
+	ENOSTR = 60,
+	/// No data available
+	ENODATA = 61,
+	/// Timer expired
+	ETIME = 62,
+	/// Out of streams resources
+	ENOSR = 63,
+	/// Machine is not on the network
+	ENONET = 64,
+	/// Package not installed
+	ENOPKG = 65,
+	/// Object is remote
+	EREMOTE = 66,
+	/// Link has been severed
+	ENOLINK = 67,
+	/// Advertise error
+	EADV = 68,
+	/// Srmount error
+	ESRMNT = 69,
+	/// Communication error on send
+	ECOMM = 70,
+	/// Protocol error
+	EPROTO = 71,
+	/// Multihop attempted
+	EMULTIHOP = 72,
+	/// RFS specific error
+	EDOTDOT = 73,
+	/// Not a data message
+	EBADMSG = 74,
+	/// Value too large for defined data type
+	EOVERFLOW = 75,
+	/// Name not unique on network
+	ENOTUNIQ = 76,
+	/// File descriptor in bad state
+	EBADFD = 77,
+	/// Remote address changed
+	EREMCHG = 78,
+	/// Can not access a needed shared library
+	ELIBACC = 79,
+	/// Accessing a corrupted shared library
+	ELIBBAD = 80,
+	/// .lib section in a.out corrupted
+	ELIBSCN = 81,
+	/// Attempting to link in too many shared libraries
+	ELIBMAX = 82,
+	/// Cannot exec a shared library directly
+	ELIBEXEC = 83,
+	/// Illegal byte sequence
+	EILSEQ = 84,
+	/// Interrupted system call should be restarted
+	ERESTART = 85,
+	/// Streams pipe error
+	ESTRPIPE = 86,
+	/// Too many users
+	EUSERS = 87,
+	/// Socket operation on non-socket
+	ENOTSOCK = 88,
+	/// Destination address required
+	EDESTADDRREQ = 89,
+	/// Message too long
+	EMSGSIZE = 90,
+	/// Protocol wrong type for socket
+	EPROTOTYPE = 91,
+	/// Protocol not available
+	ENOPROTOOPT = 92,
+	/// Protocol not supported
+	EPROTONOSUPPORT = 93,
+	/// Socket type not supported
+	ESOCKTNOSUPPORT = 94,
+	/// Operation not supported on transport endpoint
+	EOPNOTSUPP = 95,
+	/// Protocol family not supported
+	EPFNOSUPPORT = 96,
+	/// Address family not supported by protocol
+	EAFNOSUPPORT = 97,
+	/// Address already in use
+	EADDRINUSE = 98,
+	/// Cannot assign requested address
+	EADDRNOTAVAIL = 99,
+	/// Network is down
+	ENETDOWN = 100,
+	/// Network is unreachable
+	ENETUNREACH = 101,
+	/// Network dropped connection because of reset
+	ENETRESET = 102,
+	/// Software caused connection abort
+	ECONNABORTED = 103,
+	/// Connection reset by peer
+	ECONNRESET = 104,
+	/// No buffer space available
+	ENOBUFS = 105,
+	/// Transport endpoint is already connected
+	EISCONN = 106,
+	/// Transport endpoint is not connected
+	ENOTCONN = 107,
+	/// Cannot send after transport endpoint shutdown
+	ESHUTDOWN = 108,
+	/// Too many references: cannot splice
+	ETOOMANYREFS = 109,
+	/// Connection timed out
+	ETIMEDOUT = 110,
+	/// Connection refused
+	ECONNREFUSED = 111,
+	/// Host is down
+	EHOSTDOWN = 112,
+	/// No route to host
+	EHOSTUNREACH = 113,
+	/// Operation already in progress
+	EALREADY = 114,
+	/// Operation now in progress
+	EINPROGRESS = 115,
+	/// Stale file handle
+	ESTALE = 116,
+	/// Structure needs cleaning
+	EUCLEAN = 117,
+	/// Not a XENIX named type file
+	ENOTNAM = 118,
+	/// No XENIX semaphores available
+	ENAVAIL = 119,
+	/// Is a named type file
+	EISNAM = 120,
+	/// Remote I/O error
+	EREMOTEIO = 121,
+	/// Quota exceeded
+	EDQUOT = 122,
+	/// No medium found
+	ENOMEDIUM = 123,
+	/// Wrong medium type
+	EMEDIUMTYPE = 124,
+	/// Operation Canceled
+	ECANCELED = 125,
+	/// Required key not available
+	ENOKEY = 126,
+	/// Key has expired
+	EKEYEXPIRED = 127,
+	/// Key has been revoked
+	EKEYREVOKED = 128,
+	/// Key was rejected by service
+	EKEYREJECTED = 129,
+	/// Owner died
+	EOWNERDEAD = 130,
+	/// State not recoverable
+	ENOTRECOVERABLE = 131,
+	/// Operation not possible due to RF-kill
+	ERFKILL = 132,
+	/// Memory page has hardware error
+	EHWPOISON = 133,
+
+	// Aliases, and names that are not defined in asm-generic.
+
+	/// Resource deadlock would occur
+	EDEADLOCK = 1000,
+	/// Try again
+	EWOULDBLOCK = 1001,
+	/// Reserved
+	EINIT = 1002,
+	/// Error 142
+	EREMDEV = 1003,
+	/// symbol does not exist in executable
+	ENOSYM = 1004,
+	/// Remote peer released connection
+	EREMOTERELEASE = 1005,
+	/// aio request was canceled before complete (POSIX.4 / HPUX)
+	ECANCELLED = 1006,
+	/// Connection refused
+	EREFUSED = 1007,
+	/// SUNOS: Too many processes
+	EPROCLIM = 1008,
+	/// SunOS: Too many lvls of remote in path
+	ERREMOTE = 1009,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn generic_numbering() {
+		for entry in crate::arch_generic::ERRNOS {
+			let name = ErrnoName::from_name(entry.name).unwrap();
+			assert_eq!(name as u16, entry.error.get(), "{}", entry.name);
+		}
+		for &name in ErrnoName::ALL {
+			let generic = crate::arch_generic::ERRNOS
+				.iter()
+				.any(|entry| entry.name == name.as_str());
+			assert_eq!(generic, (name as u16) < 1000, "{:?}", name);
+		}
+	}
+
+	#[test]
+	fn frozen() {
+		assert_eq!(ErrnoName::EPERM as u16, 1);
+		assert_eq!(ErrnoName::ENOMSG as u16, 42);
+		assert_eq!(ErrnoName::ENOSTR as u16, 60);
+		assert_eq!(ErrnoName::EHWPOISON as u16, 133);
+		assert_eq!(ErrnoName::EDEADLOCK as u16, 1000);
+		assert_eq!(ErrnoName::EWOULDBLOCK as u16, 1001);
+		assert_eq!(ErrnoName::ERREMOTE as u16, 1009);
+	}
+
+	#[test]
+	fn every_arch_name() {
+		for &arch in Arch::ALL.iter() {
+			for entry in arch.entries() {
+				let name = ErrnoName::from_name(entry.name);
+				assert!(name.is_some(), "{} {:?}", entry.name, arch);
+				let name = name.unwrap();
+				assert_eq!(name.to_error(arch), arch.err_from_name(entry.name));
+			}
+		}
+	}
+
+	#[test]
+	fn lookups() {
+		assert!(ErrnoName::ALL.windows(2).all(|w| w[0] < w[1]));
+		for &name in ErrnoName::ALL {
+			assert_eq!(ErrnoName::from_id(name as u16), Some(name));
+			assert_eq!(ErrnoName::from_name(name.as_str()), Some(name));
+		}
+		assert_eq!(ErrnoName::from_id(0), None);
+		assert_eq!(ErrnoName::from_id(41), None);
+		assert_eq!(ErrnoName::from_name("EFOO"), None);
+	}
+
+	#[test]
+	fn name_id() {
+		let eagain = Error::new(11).unwrap();
+		assert_eq!(eagain.name_id(Arch::X86), Some(ErrnoName::EAGAIN));
+		let edquot = Error::new(1133).unwrap();
+		assert_eq!(edquot.name_id(Arch::Mips), Some(ErrnoName::EDQUOT));
+		assert_eq!(edquot.name_id(Arch::X86), None);
+		let edeadlock = Error::new(58).unwrap();
+		assert_eq!(
+			edeadlock.name_id(Arch::Powerpc),
+			Some(ErrnoName::EDEADLOCK),
+		);
+	}
+}
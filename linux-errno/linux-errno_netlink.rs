@@ -0,0 +1,427 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Decoding of netlink error messages.
+//!
+//! A netlink request that fails, or that asked for an acknowledgement with
+//! `NLM_F_ACK`, is answered by an `NLMSG_ERROR` message. Its payload is a
+//! `struct nlmsgerr`: a negated error number (or zero for an
+//! acknowledgement) followed by the header of the request, and possibly
+//! the rest of the request. If the socket has the `NETLINK_EXT_ACK` option
+//! set, the kernel appends extended acknowledgement attributes, such as a
+//! human-readable message and the offset of the request attribute that
+//! caused the error.
+//!
+//! Netlink messages use the byte order of the host. Error numbers are
+//! interpreted according to the current target architecture.
+//!
+//! ```
+//! # use linux_errno::netlink::Nlmsgerr;
+//! # use linux_errno::EINVAL;
+//! # let mut buf = Vec::new();
+//! # buf.extend_from_slice(&56u32.to_ne_bytes());
+//! # buf.extend_from_slice(&2u16.to_ne_bytes());
+//! # buf.extend_from_slice(&0x300u16.to_ne_bytes());
+//! # buf.extend_from_slice(&[0; 8]);
+//! # buf.extend_from_slice(&(-22i32).to_ne_bytes());
+//! # buf.extend_from_slice(&[0; 16]);
+//! # buf.extend_from_slice(&19u16.to_ne_bytes());
+//! # buf.extend_from_slice(&1u16.to_ne_bytes());
+//! # buf.extend_from_slice(b"Unknown device\0\0");
+//! let msg = Nlmsgerr::decode(&buf).unwrap();
+//! assert_eq!(msg.error, Some(EINVAL));
+//! assert_eq!(msg.message, Some("Unknown device"));
+//! assert_eq!(msg.to_string(), "Invalid argument: Unknown device");
+//! ```
+
+use core::fmt;
+
+use crate::Error;
+
+// https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/include/uapi/linux/netlink.h?h=v5.19
+
+/// The netlink message type of `NLMSG_ERROR`.
+pub const NLMSG_ERROR: u16 = 2;
+
+/// The `NLM_F_CAPPED` flag of an `NLMSG_ERROR` message: the payload
+/// contains only the header of the request.
+pub const NLM_F_CAPPED: u16 = 0x100;
+
+/// The `NLM_F_ACK_TLVS` flag of an `NLMSG_ERROR` message: extended
+/// acknowledgement attributes follow the request.
+pub const NLM_F_ACK_TLVS: u16 = 0x200;
+
+/// The extended acknowledgement attribute containing an error message.
+pub const NLMSGERR_ATTR_MSG: u16 = 1;
+
+/// The extended acknowledgement attribute containing the offset of the
+/// invalid attribute within the request.
+pub const NLMSGERR_ATTR_OFFS: u16 = 2;
+
+const NLMSGHDR_LEN: usize = 16;
+const NLATTR_LEN: usize = 4;
+const NLA_TYPE_MASK: u16 = 0x3FFF;
+
+/// A decoded `NLMSG_ERROR` message.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Nlmsgerr<'a> {
+	/// The error number, or `None` if the message is an acknowledgement of
+	/// a successful request.
+	pub error: Option<Error>,
+	/// The request that caused this message, starting with its header. If
+	/// the message has the `NLM_F_CAPPED` flag, only the header is present.
+	pub request: &'a [u8],
+	/// The extended acknowledgement message (`NLMSGERR_ATTR_MSG`).
+	pub message: Option<&'a str>,
+	/// The offset of the invalid attribute within the request, counting
+	/// from the start of the request header (`NLMSGERR_ATTR_OFFS`).
+	pub offset: Option<u32>,
+}
+
+impl<'a> Nlmsgerr<'a> {
+	/// Decodes an `NLMSG_ERROR` message, starting with its netlink header.
+	///
+	/// Returns `None` if `buf` does not start with a complete `NLMSG_ERROR`
+	/// message, or if the message is invalid as described for
+	/// [`decode_payload`](Self::decode_payload). Bytes after the end of
+	/// the message, as given by its `nlmsg_len`, are ignored.
+	pub fn decode(buf: &'a [u8]) -> Option<Nlmsgerr<'a>> {
+		if buf.len() < NLMSGHDR_LEN {
+			return None;
+		}
+		let len = read_u32(buf, 0) as usize;
+		let msg_type = read_u16(buf, 4);
+		let flags = read_u16(buf, 6);
+		if msg_type != NLMSG_ERROR || len < NLMSGHDR_LEN || len > buf.len() {
+			return None;
+		}
+		Nlmsgerr::decode_payload(&buf[NLMSGHDR_LEN..len], flags)
+	}
+
+	/// Decodes the payload of an `NLMSG_ERROR` message, given the flags of
+	/// its netlink header.
+	///
+	/// Returns `None` if `payload` is not a valid `struct nlmsgerr`. This
+	/// includes an `error` field that is neither zero nor a negated error
+	/// number in `[-4095, -1]`, such as a positive value. Malformed extended
+	/// acknowledgement attributes are ignored.
+	pub fn decode_payload(
+		payload: &'a [u8],
+		flags: u16,
+	) -> Option<Nlmsgerr<'a>> {
+		if payload.len() < 4 + NLMSGHDR_LEN {
+			return None;
+		}
+		let error = match read_u32(payload, 0) as i32 {
+			0 => None,
+			neg_errno => Some(Error::from_negated(neg_errno)?),
+		};
+
+		let rest = &payload[4..];
+		let request_len = if flags & NLM_F_CAPPED == 0 {
+			read_u32(rest, 0) as usize
+		} else {
+			NLMSGHDR_LEN
+		};
+		if request_len < NLMSGHDR_LEN || request_len > rest.len() {
+			return None;
+		}
+
+		let mut msg = Nlmsgerr {
+			error,
+			request: &rest[..request_len],
+			message: None,
+			offset: None,
+		};
+		if flags & NLM_F_ACK_TLVS != 0 {
+			let attrs = rest.get(align(request_len)..).unwrap_or(&[]);
+			msg.decode_attrs(attrs);
+		}
+		Some(msg)
+	}
+
+	fn decode_attrs(&mut self, mut attrs: &'a [u8]) {
+		while attrs.len() >= NLATTR_LEN {
+			let len = read_u16(attrs, 0) as usize;
+			let attr_type = read_u16(attrs, 2) & NLA_TYPE_MASK;
+			if len < NLATTR_LEN || len > attrs.len() {
+				return;
+			}
+			let value = &attrs[NLATTR_LEN..len];
+			match attr_type {
+				NLMSGERR_ATTR_MSG => {
+					let value = match value.split_last() {
+						Some((0, value)) => value,
+						_ => value,
+					};
+					self.message = core::str::from_utf8(value).ok();
+				},
+				NLMSGERR_ATTR_OFFS if value.len() == 4 => {
+					self.offset = Some(read_u32(value, 0));
+				},
+				_ => {},
+			}
+			attrs = attrs.get(align(len)..).unwrap_or(&[]);
+		}
+	}
+}
+
+/// Formats the description of the error number, followed by the extended
+/// acknowledgement message if present, such as
+/// `"Invalid argument: Unknown device"`.
+///
+/// An acknowledgement is formatted as `"Success"`.
+impl fmt::Display for Nlmsgerr<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.error {
+			Some(err) => fmt::Display::fmt(&err, f)?,
+			None => f.write_str("Success")?,
+		}
+		match self.message {
+			Some(message) => write!(f, ": {}", message),
+			None => Ok(()),
+		}
+	}
+}
+
+#[inline]
+const fn align(len: usize) -> usize {
+	(len + 3) & !3
+}
+
+#[inline]
+fn read_u16(buf: &[u8], idx: usize) -> u16 {
+	u16::from_ne_bytes([buf[idx], buf[idx + 1]])
+}
+
+#[inline]
+fn read_u32(buf: &[u8], idx: usize) -> u32 {
+	u32::from_ne_bytes([buf[idx], buf[idx + 1], buf[idx + 2], buf[idx + 3]])
+}
+
+#[cfg(test)]
+mod tests {
+	use std::string::ToString;
+	use std::vec::Vec;
+
+	use super::*;
+	use crate::{EINVAL, ENOENT};
+
+	// A request with the given payload, starting with its netlink header.
+	fn request(payload: &[u8]) -> Vec<u8> {
+		let mut buf = Vec::new();
+		let len = (NLMSGHDR_LEN + payload.len()) as u32;
+		buf.extend_from_slice(&len.to_ne_bytes());
+		buf.extend_from_slice(&[0; 12]);
+		buf.extend_from_slice(payload);
+		buf
+	}
+
+	fn attr(attr_type: u16, value: &[u8]) -> Vec<u8> {
+		let mut buf = Vec::new();
+		let len = (NLATTR_LEN + value.len()) as u16;
+		buf.extend_from_slice(&len.to_ne_bytes());
+		buf.extend_from_slice(&attr_type.to_ne_bytes());
+		buf.extend_from_slice(value);
+		buf.resize(align(buf.len()), 0);
+		buf
+	}
+
+	fn message(
+		error: i32,
+		flags: u16,
+		request: &[u8],
+		attrs: &[u8],
+	) -> Vec<u8> {
+		let mut payload = Vec::new();
+		payload.extend_from_slice(&error.to_ne_bytes());
+		payload.extend_from_slice(request);
+		payload.resize(align(payload.len()), 0);
+		payload.extend_from_slice(attrs);
+
+		let mut buf = Vec::new();
+		let len = (NLMSGHDR_LEN + payload.len()) as u32;
+		buf.extend_from_slice(&len.to_ne_bytes());
+		buf.extend_from_slice(&NLMSG_ERROR.to_ne_bytes());
+		buf.extend_from_slice(&flags.to_ne_bytes());
+		buf.extend_from_slice(&[0; 8]);
+		buf.extend_from_slice(&payload);
+		buf
+	}
+
+	#[test]
+	fn ack() {
+		let req = request(b"abc");
+		let buf = message(0, 0, &req, &[]);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.error, None);
+		assert_eq!(msg.request, &req[..]);
+		assert_eq!(msg.message, None);
+		assert_eq!(msg.offset, None);
+		assert_eq!(msg.to_string(), "Success");
+	}
+
+	#[test]
+	fn error() {
+		let req = request(&[0; 8]);
+		let buf = message(-2, 0, &req, &[]);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.error, Some(ENOENT));
+		assert_eq!(msg.request, &req[..]);
+		assert_eq!(msg.to_string(), "No such file or directory");
+	}
+
+	#[test]
+	fn capped() {
+		let req = request(&[0; 8]);
+		let offs = 20u32.to_ne_bytes();
+		let mut attrs = attr(NLMSGERR_ATTR_MSG, b"bad\0");
+		attrs.extend_from_slice(&attr(NLMSGERR_ATTR_OFFS, &offs));
+		let flags = NLM_F_CAPPED | NLM_F_ACK_TLVS;
+		let buf = message(-22, flags, &req[..NLMSGHDR_LEN], &attrs);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.error, Some(EINVAL));
+		assert_eq!(msg.request, &req[..NLMSGHDR_LEN]);
+		assert_eq!(msg.message, Some("bad"));
+		assert_eq!(msg.offset, Some(20));
+		assert_eq!(msg.to_string(), "Invalid argument: bad");
+	}
+
+	#[test]
+	fn attrs() {
+		let req = request(b"abcde");
+		let offs = 20u32.to_ne_bytes();
+
+		// Attributes are ignored without `NLM_F_ACK_TLVS`.
+		let attrs = attr(NLMSGERR_ATTR_OFFS, &offs);
+		let buf = message(-22, 0, &req, &attrs);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.request, &req[..]);
+		assert_eq!(msg.offset, None);
+
+		// A message without a trailing NUL.
+		let mut attrs = attr(NLMSGERR_ATTR_OFFS, &offs);
+		attrs.extend_from_slice(&attr(NLMSGERR_ATTR_MSG, b"no NUL"));
+		let buf = message(-22, NLM_F_ACK_TLVS, &req, &attrs);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.offset, Some(20));
+		assert_eq!(msg.message, Some("no NUL"));
+
+		// Flags in the attribute type, and unknown attributes.
+		let mut attrs = attr(0x8000 | NLMSGERR_ATTR_OFFS, &offs);
+		attrs.extend_from_slice(&attr(3, b"?"));
+		let buf = message(-22, NLM_F_ACK_TLVS, &req, &attrs);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.offset, Some(20));
+		assert_eq!(msg.message, None);
+
+		// An offset of the wrong size, and a message that is not UTF-8.
+		let mut attrs = attr(NLMSGERR_ATTR_OFFS, &[0; 2]);
+		attrs.extend_from_slice(&attr(NLMSGERR_ATTR_MSG, b"\xFF\0"));
+		let buf = message(-22, NLM_F_ACK_TLVS, &req, &attrs);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.offset, None);
+		assert_eq!(msg.message, None);
+	}
+
+	#[test]
+	fn truncated_attrs() {
+		let req = request(&[]);
+		let mut attrs = attr(NLMSGERR_ATTR_OFFS, &20u32.to_ne_bytes());
+		attrs.extend_from_slice(&attr(NLMSGERR_ATTR_MSG, b"truncated\0"));
+
+		// Attributes before the truncated one are kept, and the padding
+		// after the last attribute may be missing.
+		for cut in 1..attrs.len() {
+			let buf = message(-22, NLM_F_ACK_TLVS, &req, &attrs[..cut]);
+			let msg = Nlmsgerr::decode(&buf).unwrap();
+			assert_eq!(msg.error, Some(EINVAL));
+			assert_eq!(msg.offset.is_some(), cut >= 8);
+			assert_eq!(msg.message.is_some(), cut >= 8 + 14);
+		}
+
+		// An attribute length shorter than the attribute header.
+		let mut attrs = attr(NLMSGERR_ATTR_MSG, b"x\0");
+		attrs[..2].copy_from_slice(&2u16.to_ne_bytes());
+		let buf = message(-22, NLM_F_ACK_TLVS, &req, &attrs);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.message, None);
+	}
+
+	#[test]
+	fn truncated() {
+		let req = request(b"abcd");
+		let buf = message(-22, 0, &req, &[]);
+		for cut in 0..buf.len() {
+			assert_eq!(Nlmsgerr::decode(&buf[..cut]), None);
+		}
+
+		// A payload too short for the request header.
+		let buf = message(-22, NLM_F_CAPPED, &req[..8], &[]);
+		assert_eq!(Nlmsgerr::decode(&buf), None);
+
+		// A request longer than the payload.
+		let buf = message(-22, 0, &req[..NLMSGHDR_LEN], &[]);
+		assert_eq!(Nlmsgerr::decode(&buf), None);
+
+		// A request length shorter than its header.
+		let mut bad = req.clone();
+		bad[..4].copy_from_slice(&8u32.to_ne_bytes());
+		let buf = message(-22, 0, &bad, &[]);
+		assert_eq!(Nlmsgerr::decode(&buf), None);
+
+		// A message length shorter than its header.
+		let mut buf = message(-22, 0, &req, &[]);
+		buf[..4].copy_from_slice(&8u32.to_ne_bytes());
+		assert_eq!(Nlmsgerr::decode(&buf), None);
+	}
+
+	#[test]
+	fn trailing_bytes() {
+		let req = request(&[]);
+		let attrs = attr(NLMSGERR_ATTR_MSG, b"kept\0");
+		let buf = message(-22, NLM_F_ACK_TLVS, &req, &attrs);
+		let expect = Nlmsgerr::decode(&buf).unwrap();
+
+		// Another message after the end of this one is ignored, even if it
+		// looks like an attribute.
+		let mut longer = buf.clone();
+		longer.extend_from_slice(&attr(NLMSGERR_ATTR_OFFS, &[0; 4]));
+		assert_eq!(Nlmsgerr::decode(&longer), Some(expect));
+	}
+
+	#[test]
+	fn invalid_error() {
+		let req = request(&[]);
+		for &error in [1, 22, i32::MAX, -4096, i32::MIN].iter() {
+			let buf = message(error, 0, &req, &[]);
+			assert_eq!(Nlmsgerr::decode(&buf), None, "{}", error);
+		}
+		let buf = message(-4095, 0, &req, &[]);
+		let msg = Nlmsgerr::decode(&buf).unwrap();
+		assert_eq!(msg.error, Error::new(4095));
+	}
+
+	#[test]
+	fn wrong_type() {
+		let req = request(&[]);
+		let mut buf = message(-22, 0, &req, &[]);
+		buf[4..6].copy_from_slice(&3u16.to_ne_bytes());
+		assert_eq!(Nlmsgerr::decode(&buf), None);
+
+		let msg = Nlmsgerr::decode_payload(&buf[NLMSGHDR_LEN..], 0).unwrap();
+		assert_eq!(msg.error, Some(EINVAL));
+	}
+}
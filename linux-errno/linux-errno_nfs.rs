@@ -0,0 +1,498 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Conversions between Linux error numbers and NFS status codes.
+//!
+//! The conversions follow the Linux kernel's NFS server (`nfserrno()` in
+//! `fs/nfsd/nfsproc.c`) and NFS client (`nfs_errtbl` in `fs/nfs/nfs2xdr.c`
+//! and `fs/nfs/nfs4xdr.c`, plus `nfs4_map_errors()` in `fs/nfs/nfs4proc.c`).
+//!
+//! Several NFS status codes are converted to the kernel-internal error
+//! numbers in the [`kernel`] module. For example, both [`NFS3ERR_JUKEBOX`]
+//! and [`NFS4ERR_DELAY`] are converted to [`EJUKEBOX`](kernel::EJUKEBOX),
+//! which user-space callers will usually want to treat as `EAGAIN`.
+//!
+//! Both conversions are lossy. Several error numbers share a status code:
+//! for example `EAGAIN`, `ENOMEM`, and `ETIMEDOUT` are all converted to
+//! `NFS3ERR_JUKEBOX`. NFSv4 status codes with no Linux equivalent, such as
+//! `NFS4ERR_LOCKED` or `NFS4ERR_WRONGSEC`, are converted to a related error
+//! number that converts back to a different status code.
+//!
+//! Linux error numbers are interpreted according to the current target
+//! architecture.
+
+use crate::Error;
+use crate::kernel;
+use crate::target;
+
+// https://www.rfc-editor.org/rfc/rfc1813#section-2.6
+
+pub const NFS3ERR_PERM: u32 = 1;
+pub const NFS3ERR_NOENT: u32 = 2;
+pub const NFS3ERR_IO: u32 = 5;
+pub const NFS3ERR_NXIO: u32 = 6;
+pub const NFS3ERR_ACCES: u32 = 13;
+pub const NFS3ERR_EXIST: u32 = 17;
+pub const NFS3ERR_XDEV: u32 = 18;
+pub const NFS3ERR_NODEV: u32 = 19;
+pub const NFS3ERR_NOTDIR: u32 = 20;
+pub const NFS3ERR_ISDIR: u32 = 21;
+pub const NFS3ERR_INVAL: u32 = 22;
+pub const NFS3ERR_FBIG: u32 = 27;
+pub const NFS3ERR_NOSPC: u32 = 28;
+pub const NFS3ERR_ROFS: u32 = 30;
+pub const NFS3ERR_MLINK: u32 = 31;
+pub const NFS3ERR_NAMETOOLONG: u32 = 63;
+pub const NFS3ERR_NOTEMPTY: u32 = 66;
+pub const NFS3ERR_DQUOT: u32 = 69;
+pub const NFS3ERR_STALE: u32 = 70;
+pub const NFS3ERR_REMOTE: u32 = 71;
+pub const NFS3ERR_BADHANDLE: u32 = 10001;
+pub const NFS3ERR_NOT_SYNC: u32 = 10002;
+pub const NFS3ERR_BAD_COOKIE: u32 = 10003;
+pub const NFS3ERR_NOTSUPP: u32 = 10004;
+pub const NFS3ERR_TOOSMALL: u32 = 10005;
+pub const NFS3ERR_SERVERFAULT: u32 = 10006;
+pub const NFS3ERR_BADTYPE: u32 = 10007;
+pub const NFS3ERR_JUKEBOX: u32 = 10008;
+
+// https://www.rfc-editor.org/rfc/rfc7530#section-13.1
+// https://www.rfc-editor.org/rfc/rfc8881#section-15.1
+// https://www.rfc-editor.org/rfc/rfc8276#section-8.5
+
+pub const NFS4ERR_PERM: u32 = 1;
+pub const NFS4ERR_NOENT: u32 = 2;
+pub const NFS4ERR_IO: u32 = 5;
+pub const NFS4ERR_NXIO: u32 = 6;
+pub const NFS4ERR_ACCESS: u32 = 13;
+pub const NFS4ERR_EXIST: u32 = 17;
+pub const NFS4ERR_XDEV: u32 = 18;
+pub const NFS4ERR_NOTDIR: u32 = 20;
+pub const NFS4ERR_ISDIR: u32 = 21;
+pub const NFS4ERR_INVAL: u32 = 22;
+pub const NFS4ERR_FBIG: u32 = 27;
+pub const NFS4ERR_NOSPC: u32 = 28;
+pub const NFS4ERR_ROFS: u32 = 30;
+pub const NFS4ERR_MLINK: u32 = 31;
+pub const NFS4ERR_NAMETOOLONG: u32 = 63;
+pub const NFS4ERR_NOTEMPTY: u32 = 66;
+pub const NFS4ERR_DQUOT: u32 = 69;
+pub const NFS4ERR_STALE: u32 = 70;
+pub const NFS4ERR_BADHANDLE: u32 = 10001;
+pub const NFS4ERR_BAD_COOKIE: u32 = 10003;
+pub const NFS4ERR_NOTSUPP: u32 = 10004;
+pub const NFS4ERR_TOOSMALL: u32 = 10005;
+pub const NFS4ERR_SERVERFAULT: u32 = 10006;
+pub const NFS4ERR_BADTYPE: u32 = 10007;
+pub const NFS4ERR_DELAY: u32 = 10008;
+pub const NFS4ERR_LOCKED: u32 = 10012;
+pub const NFS4ERR_SHARE_DENIED: u32 = 10015;
+pub const NFS4ERR_WRONGSEC: u32 = 10016;
+pub const NFS4ERR_RESOURCE: u32 = 10018;
+pub const NFS4ERR_MINOR_VERS_MISMATCH: u32 = 10021;
+pub const NFS4ERR_NOT_SAME: u32 = 10027;
+pub const NFS4ERR_SYMLINK: u32 = 10029;
+pub const NFS4ERR_NO_GRACE: u32 = 10033;
+pub const NFS4ERR_BADOWNER: u32 = 10039;
+pub const NFS4ERR_BADNAME: u32 = 10041;
+pub const NFS4ERR_OP_ILLEGAL: u32 = 10044;
+pub const NFS4ERR_DEADLOCK: u32 = 10045;
+pub const NFS4ERR_FILE_OPEN: u32 = 10046;
+pub const NFS4ERR_LAYOUTTRYLATER: u32 = 10058;
+pub const NFS4ERR_RECALLCONFLICT: u32 = 10061;
+pub const NFS4ERR_WRONG_CRED: u32 = 10082;
+pub const NFS4ERR_RETURNCONFLICT: u32 = 10086;
+pub const NFS4ERR_NOXATTR: u32 = 10095;
+pub const NFS4ERR_XATTR2BIG: u32 = 10096;
+
+/// Converts a Linux error number to an NFSv3 status code (`nfsstat3`).
+///
+/// Error numbers without a more specific status code are converted to
+/// `NFS3ERR_IO`.
+pub const fn to_nfsstat3(err: Error) -> u32 {
+	match err {
+		target::EPERM        => NFS3ERR_PERM,
+		target::ENOENT       => NFS3ERR_NOENT,
+		target::EIO          => NFS3ERR_IO,
+		target::ENXIO        => NFS3ERR_NXIO,
+		target::E2BIG        => NFS3ERR_FBIG,
+		target::EBADF        => NFS3ERR_STALE,
+		target::EACCES       => NFS3ERR_ACCES,
+		target::EEXIST       => NFS3ERR_EXIST,
+		target::EXDEV        => NFS3ERR_XDEV,
+		target::ENODEV       => NFS3ERR_NODEV,
+		target::ENOTDIR      => NFS3ERR_NOTDIR,
+		target::EISDIR       => NFS3ERR_ISDIR,
+		target::EINVAL       => NFS3ERR_INVAL,
+		target::EFBIG        => NFS3ERR_FBIG,
+		target::ENOSPC       => NFS3ERR_NOSPC,
+		target::EROFS        => NFS3ERR_ROFS,
+		target::EMLINK       => NFS3ERR_MLINK,
+		target::ENAMETOOLONG => NFS3ERR_NAMETOOLONG,
+		target::ENOTEMPTY    => NFS3ERR_NOTEMPTY,
+		target::EDQUOT       => NFS3ERR_DQUOT,
+		target::ESTALE       => NFS3ERR_STALE,
+		target::EREMOTE      => NFS3ERR_REMOTE,
+		target::ETIMEDOUT    => NFS3ERR_JUKEBOX,
+		target::EAGAIN       => NFS3ERR_JUKEBOX,
+		target::ENOMEM       => NFS3ERR_JUKEBOX,
+		target::EOPNOTSUPP   => NFS3ERR_NOTSUPP,
+		target::ENFILE       => NFS3ERR_SERVERFAULT,
+		target::ENOKEY       => NFS3ERR_PERM,
+		kernel::ERESTARTSYS  => NFS3ERR_JUKEBOX,
+		kernel::EOPENSTALE   => NFS3ERR_STALE,
+		kernel::EBADHANDLE   => NFS3ERR_BADHANDLE,
+		kernel::ENOTSYNC     => NFS3ERR_NOT_SYNC,
+		kernel::EBADCOOKIE   => NFS3ERR_BAD_COOKIE,
+		kernel::ENOTSUPP     => NFS3ERR_NOTSUPP,
+		kernel::ETOOSMALL    => NFS3ERR_TOOSMALL,
+		kernel::ESERVERFAULT => NFS3ERR_SERVERFAULT,
+		kernel::EBADTYPE     => NFS3ERR_BADTYPE,
+		kernel::EJUKEBOX     => NFS3ERR_JUKEBOX,
+		_ => NFS3ERR_IO,
+	}
+}
+
+/// Converts an NFSv3 status code (`nfsstat3`) to a Linux error number.
+///
+/// Returns `None` for `NFS3_OK`. Unknown status codes are converted to
+/// `EIO`.
+pub const fn from_nfsstat3(stat: u32) -> Option<Error> {
+	Some(match stat {
+		0                   => return None,
+		NFS3ERR_PERM        => target::EPERM,
+		NFS3ERR_NOENT       => target::ENOENT,
+		NFS3ERR_IO          => target::EIO,
+		NFS3ERR_NXIO        => target::ENXIO,
+		NFS3ERR_ACCES       => target::EACCES,
+		NFS3ERR_EXIST       => target::EEXIST,
+		NFS3ERR_XDEV        => target::EXDEV,
+		NFS3ERR_NODEV       => target::ENODEV,
+		NFS3ERR_NOTDIR      => target::ENOTDIR,
+		NFS3ERR_ISDIR       => target::EISDIR,
+		NFS3ERR_INVAL       => target::EINVAL,
+		NFS3ERR_FBIG        => target::EFBIG,
+		NFS3ERR_NOSPC       => target::ENOSPC,
+		NFS3ERR_ROFS        => target::EROFS,
+		NFS3ERR_MLINK       => target::EMLINK,
+		NFS3ERR_NAMETOOLONG => target::ENAMETOOLONG,
+		NFS3ERR_NOTEMPTY    => target::ENOTEMPTY,
+		NFS3ERR_DQUOT       => target::EDQUOT,
+		NFS3ERR_STALE       => target::ESTALE,
+		NFS3ERR_REMOTE      => target::EREMOTE,
+		NFS3ERR_BADHANDLE   => kernel::EBADHANDLE,
+		NFS3ERR_NOT_SYNC    => kernel::ENOTSYNC,
+		NFS3ERR_BAD_COOKIE  => kernel::EBADCOOKIE,
+		NFS3ERR_NOTSUPP     => kernel::ENOTSUPP,
+		NFS3ERR_TOOSMALL    => kernel::ETOOSMALL,
+		NFS3ERR_SERVERFAULT => target::EREMOTEIO,
+		NFS3ERR_BADTYPE     => kernel::EBADTYPE,
+		NFS3ERR_JUKEBOX     => kernel::EJUKEBOX,
+		_ => target::EIO,
+	})
+}
+
+/// Converts a Linux error number to an NFSv4 status code (`nfsstat4`).
+///
+/// Error numbers without a more specific status code are converted to
+/// `NFS4ERR_IO`.
+pub const fn to_nfsstat4(err: Error) -> u32 {
+	match err {
+		target::EPERM        => NFS4ERR_PERM,
+		target::ENOENT       => NFS4ERR_NOENT,
+		target::EIO          => NFS4ERR_IO,
+		target::ENXIO        => NFS4ERR_NXIO,
+		target::E2BIG        => NFS4ERR_FBIG,
+		target::EBADF        => NFS4ERR_STALE,
+		target::EACCES       => NFS4ERR_ACCESS,
+		target::EEXIST       => NFS4ERR_EXIST,
+		target::EXDEV        => NFS4ERR_XDEV,
+		target::ENODEV       => NFS4ERR_NXIO,
+		target::ENOTDIR      => NFS4ERR_NOTDIR,
+		target::EISDIR       => NFS4ERR_ISDIR,
+		target::EINVAL       => NFS4ERR_INVAL,
+		target::EFBIG        => NFS4ERR_FBIG,
+		target::ENOSPC       => NFS4ERR_NOSPC,
+		target::EROFS        => NFS4ERR_ROFS,
+		target::EMLINK       => NFS4ERR_MLINK,
+		target::ENAMETOOLONG => NFS4ERR_NAMETOOLONG,
+		target::ENOTEMPTY    => NFS4ERR_NOTEMPTY,
+		target::EDQUOT       => NFS4ERR_DQUOT,
+		target::ESTALE       => NFS4ERR_STALE,
+		target::ETIMEDOUT    => NFS4ERR_DELAY,
+		target::EAGAIN       => NFS4ERR_DELAY,
+		target::ENOMEM       => NFS4ERR_DELAY,
+		target::EOPNOTSUPP   => NFS4ERR_NOTSUPP,
+		target::ENFILE       => NFS4ERR_SERVERFAULT,
+		target::ENOKEY       => NFS4ERR_PERM,
+		target::ELOOP        => NFS4ERR_SYMLINK,
+		target::EDEADLK      => NFS4ERR_DEADLOCK,
+		target::ENODATA      => NFS4ERR_NOXATTR,
+		kernel::ERESTARTSYS  => NFS4ERR_DELAY,
+		kernel::EOPENSTALE   => NFS4ERR_STALE,
+		kernel::EBADHANDLE   => NFS4ERR_BADHANDLE,
+		kernel::EBADCOOKIE   => NFS4ERR_BAD_COOKIE,
+		kernel::ENOTSUPP     => NFS4ERR_NOTSUPP,
+		kernel::ETOOSMALL    => NFS4ERR_TOOSMALL,
+		kernel::ESERVERFAULT => NFS4ERR_SERVERFAULT,
+		kernel::EBADTYPE     => NFS4ERR_BADTYPE,
+		kernel::EJUKEBOX     => NFS4ERR_DELAY,
+		kernel::ENOGRACE     => NFS4ERR_NO_GRACE,
+		_ => NFS4ERR_IO,
+	}
+}
+
+/// Converts an NFSv4 status code (`nfsstat4`) to a Linux error number.
+///
+/// Returns `None` for `NFS4_OK`. Unknown status codes, and status codes that
+/// the kernel's NFS client handles internally (such as state recovery), are
+/// converted to `EIO`.
+pub const fn from_nfsstat4(stat: u32) -> Option<Error> {
+	Some(match stat {
+		0                           => return None,
+		NFS4ERR_PERM                => target::EPERM,
+		NFS4ERR_NOENT               => target::ENOENT,
+		NFS4ERR_IO                  => target::EIO,
+		NFS4ERR_NXIO                => target::ENXIO,
+		NFS4ERR_ACCESS              => target::EACCES,
+		NFS4ERR_EXIST               => target::EEXIST,
+		NFS4ERR_XDEV                => target::EXDEV,
+		NFS4ERR_NOTDIR              => target::ENOTDIR,
+		NFS4ERR_ISDIR               => target::EISDIR,
+		NFS4ERR_INVAL               => target::EINVAL,
+		NFS4ERR_FBIG                => target::EFBIG,
+		NFS4ERR_NOSPC               => target::ENOSPC,
+		NFS4ERR_ROFS                => target::EROFS,
+		NFS4ERR_MLINK               => target::EMLINK,
+		NFS4ERR_NAMETOOLONG         => target::ENAMETOOLONG,
+		NFS4ERR_NOTEMPTY            => target::ENOTEMPTY,
+		NFS4ERR_DQUOT               => target::EDQUOT,
+		NFS4ERR_STALE               => target::ESTALE,
+		NFS4ERR_BADHANDLE           => kernel::EBADHANDLE,
+		NFS4ERR_BAD_COOKIE          => kernel::EBADCOOKIE,
+		NFS4ERR_NOTSUPP             => kernel::ENOTSUPP,
+		NFS4ERR_TOOSMALL            => kernel::ETOOSMALL,
+		NFS4ERR_SERVERFAULT         => target::EREMOTEIO,
+		NFS4ERR_BADTYPE             => kernel::EBADTYPE,
+		NFS4ERR_DELAY               => kernel::EJUKEBOX,
+		NFS4ERR_LOCKED              => target::EAGAIN,
+		NFS4ERR_SHARE_DENIED        => target::EACCES,
+		NFS4ERR_WRONGSEC            => target::EPERM,
+		NFS4ERR_RESOURCE            => target::EREMOTEIO,
+		NFS4ERR_MINOR_VERS_MISMATCH => target::EPROTONOSUPPORT,
+		NFS4ERR_NOT_SAME            => kernel::ENOTSYNC,
+		NFS4ERR_SYMLINK             => target::ELOOP,
+		NFS4ERR_NO_GRACE            => kernel::ENOGRACE,
+		NFS4ERR_BADOWNER            => target::EINVAL,
+		NFS4ERR_BADNAME             => target::EINVAL,
+		NFS4ERR_OP_ILLEGAL          => target::EOPNOTSUPP,
+		NFS4ERR_DEADLOCK            => target::EDEADLK,
+		NFS4ERR_FILE_OPEN           => target::EBUSY,
+		NFS4ERR_LAYOUTTRYLATER      => target::EREMOTEIO,
+		NFS4ERR_RECALLCONFLICT      => target::EREMOTEIO,
+		NFS4ERR_WRONG_CRED          => target::EPERM,
+		NFS4ERR_RETURNCONFLICT      => target::EREMOTEIO,
+		NFS4ERR_NOXATTR             => target::ENODATA,
+		NFS4ERR_XATTR2BIG           => target::E2BIG,
+		_ => target::EIO,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const NFSSTAT3: &[u32] = &[
+		NFS3ERR_PERM,
+		NFS3ERR_NOENT,
+		NFS3ERR_IO,
+		NFS3ERR_NXIO,
+		NFS3ERR_ACCES,
+		NFS3ERR_EXIST,
+		NFS3ERR_XDEV,
+		NFS3ERR_NODEV,
+		NFS3ERR_NOTDIR,
+		NFS3ERR_ISDIR,
+		NFS3ERR_INVAL,
+		NFS3ERR_FBIG,
+		NFS3ERR_NOSPC,
+		NFS3ERR_ROFS,
+		NFS3ERR_MLINK,
+		NFS3ERR_NAMETOOLONG,
+		NFS3ERR_NOTEMPTY,
+		NFS3ERR_DQUOT,
+		NFS3ERR_STALE,
+		NFS3ERR_REMOTE,
+		NFS3ERR_BADHANDLE,
+		NFS3ERR_NOT_SYNC,
+		NFS3ERR_BAD_COOKIE,
+		NFS3ERR_NOTSUPP,
+		NFS3ERR_TOOSMALL,
+		NFS3ERR_SERVERFAULT,
+		NFS3ERR_BADTYPE,
+		NFS3ERR_JUKEBOX,
+	];
+
+	const NFSSTAT4: &[u32] = &[
+		NFS4ERR_PERM,
+		NFS4ERR_NOENT,
+		NFS4ERR_IO,
+		NFS4ERR_NXIO,
+		NFS4ERR_ACCESS,
+		NFS4ERR_EXIST,
+		NFS4ERR_XDEV,
+		NFS4ERR_NOTDIR,
+		NFS4ERR_ISDIR,
+		NFS4ERR_INVAL,
+		NFS4ERR_FBIG,
+		NFS4ERR_NOSPC,
+		NFS4ERR_ROFS,
+		NFS4ERR_MLINK,
+		NFS4ERR_NAMETOOLONG,
+		NFS4ERR_NOTEMPTY,
+		NFS4ERR_DQUOT,
+		NFS4ERR_STALE,
+		NFS4ERR_BADHANDLE,
+		NFS4ERR_BAD_COOKIE,
+		NFS4ERR_NOTSUPP,
+		NFS4ERR_TOOSMALL,
+		NFS4ERR_SERVERFAULT,
+		NFS4ERR_BADTYPE,
+		NFS4ERR_DELAY,
+		NFS4ERR_LOCKED,
+		NFS4ERR_SHARE_DENIED,
+		NFS4ERR_WRONGSEC,
+		NFS4ERR_RESOURCE,
+		NFS4ERR_MINOR_VERS_MISMATCH,
+		NFS4ERR_NOT_SAME,
+		NFS4ERR_SYMLINK,
+		NFS4ERR_NO_GRACE,
+		NFS4ERR_BADOWNER,
+		NFS4ERR_BADNAME,
+		NFS4ERR_OP_ILLEGAL,
+		NFS4ERR_DEADLOCK,
+		NFS4ERR_FILE_OPEN,
+		NFS4ERR_LAYOUTTRYLATER,
+		NFS4ERR_RECALLCONFLICT,
+		NFS4ERR_WRONG_CRED,
+		NFS4ERR_RETURNCONFLICT,
+		NFS4ERR_NOXATTR,
+		NFS4ERR_XATTR2BIG,
+	];
+
+	// Status codes that convert to an error number with a different (or no
+	// more specific) status code.
+	const NFSSTAT3_LOSSY: &[u32] = &[NFS3ERR_SERVERFAULT];
+	const NFSSTAT4_LOSSY: &[u32] = &[
+		NFS4ERR_SERVERFAULT,
+		NFS4ERR_LOCKED,
+		NFS4ERR_SHARE_DENIED,
+		NFS4ERR_WRONGSEC,
+		NFS4ERR_RESOURCE,
+		NFS4ERR_MINOR_VERS_MISMATCH,
+		NFS4ERR_NOT_SAME,
+		NFS4ERR_BADOWNER,
+		NFS4ERR_BADNAME,
+		NFS4ERR_OP_ILLEGAL,
+		NFS4ERR_FILE_OPEN,
+		NFS4ERR_LAYOUTTRYLATER,
+		NFS4ERR_RECALLCONFLICT,
+		NFS4ERR_WRONG_CRED,
+		NFS4ERR_RETURNCONFLICT,
+		NFS4ERR_XATTR2BIG,
+	];
+
+	// Error numbers that share a status code with another error number, and
+	// the error number that the status code converts back to.
+	const ERRNO_LOSSY: &[(Error, Error)] = &[
+		(target::E2BIG, target::EFBIG),
+		(target::EBADF, target::ESTALE),
+		(target::EAGAIN, kernel::EJUKEBOX),
+		(target::ENOMEM, kernel::EJUKEBOX),
+		(target::ETIMEDOUT, kernel::EJUKEBOX),
+		(target::ENFILE, target::EREMOTEIO),
+		(target::EOPNOTSUPP, kernel::ENOTSUPP),
+		(target::ENOKEY, target::EPERM),
+		(kernel::ERESTARTSYS, kernel::EJUKEBOX),
+		(kernel::EOPENSTALE, target::ESTALE),
+		(kernel::ESERVERFAULT, target::EREMOTEIO),
+	];
+
+	fn round_trip(err: Error) -> Error {
+		match ERRNO_LOSSY.iter().find(|(from, _)| *from == err) {
+			Some((_, to)) => *to,
+			None => err,
+		}
+	}
+
+	#[test]
+	fn nfsstat3_round_trip() {
+		for &stat in NFSSTAT3 {
+			let err = from_nfsstat3(stat).unwrap();
+			if NFSSTAT3_LOSSY.contains(&stat) {
+				assert_ne!(to_nfsstat3(err), stat, "{}", stat);
+			} else {
+				assert_eq!(to_nfsstat3(err), stat, "{}", stat);
+			}
+		}
+	}
+
+	#[test]
+	fn nfsstat4_round_trip() {
+		for &stat in NFSSTAT4 {
+			let err = from_nfsstat4(stat).unwrap();
+			if NFSSTAT4_LOSSY.contains(&stat) {
+				assert_ne!(to_nfsstat4(err), stat, "{}", stat);
+			} else {
+				assert_eq!(to_nfsstat4(err), stat, "{}", stat);
+			}
+		}
+	}
+
+	#[test]
+	fn errno_round_trip() {
+		for err in (1..0x1000).filter_map(Error::new) {
+			let stat = to_nfsstat3(err);
+			if stat != NFS3ERR_IO {
+				assert_eq!(from_nfsstat3(stat), Some(round_trip(err)));
+			}
+			let stat = to_nfsstat4(err);
+			if stat != NFS4ERR_IO && err != target::ENODEV {
+				assert_eq!(from_nfsstat4(stat), Some(round_trip(err)));
+			}
+		}
+		let stat = to_nfsstat4(target::ENODEV);
+		assert_eq!(from_nfsstat4(stat), Some(target::ENXIO));
+	}
+
+	#[test]
+	fn delay() {
+		assert_eq!(from_nfsstat3(NFS3ERR_JUKEBOX), Some(kernel::EJUKEBOX));
+		assert_eq!(from_nfsstat4(NFS4ERR_DELAY), Some(kernel::EJUKEBOX));
+		assert_eq!(to_nfsstat4(kernel::EJUKEBOX), NFS4ERR_DELAY);
+		assert_eq!(to_nfsstat4(target::EAGAIN), NFS4ERR_DELAY);
+		assert_eq!(to_nfsstat3(target::EAGAIN), NFS3ERR_JUKEBOX);
+	}
+
+	#[test]
+	fn ok_and_unknown() {
+		assert_eq!(from_nfsstat3(0), None);
+		assert_eq!(from_nfsstat4(0), None);
+		assert_eq!(from_nfsstat3(12345), Some(target::EIO));
+		assert_eq!(from_nfsstat4(12345), Some(target::EIO));
+		assert_eq!(to_nfsstat3(target::EHWPOISON), NFS3ERR_IO);
+		assert_eq!(to_nfsstat4(target::EHWPOISON), NFS4ERR_IO);
+	}
+}
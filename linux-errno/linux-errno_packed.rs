@@ -0,0 +1,397 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use core::convert::TryFrom;
+use core::{fmt, hash};
+
+use crate::Error;
+
+// https://git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux.git/tree/include/linux/err.h?h=v5.19
+
+const MAX_ERRNO: usize = 0xFFF;
+
+#[inline]
+const fn is_err_value(raw: usize) -> bool {
+	raw > usize::MAX - MAX_ERRNO
+}
+
+#[inline]
+const fn err_value(err: Error) -> usize {
+	0usize.wrapping_sub(err.get() as usize)
+}
+
+#[inline]
+const fn raw_err(raw: usize) -> Option<Error> {
+	if is_err_value(raw) {
+		Error::new(0usize.wrapping_sub(raw) as u16)
+	} else {
+		None
+	}
+}
+
+/// A value or an error number, packed into a single `usize`.
+///
+/// This is the encoding used by the Linux kernel's `IS_ERR_VALUE()` and by
+/// the raw return values of system calls: the top 4095 values of `usize`
+/// are the negated error numbers `-4095 ..= -1`, and every other value is a
+/// successful result.
+///
+/// Unlike `Result<usize, Error>`, an `ErrOrUsize` has the same size and ABI
+/// as a `usize`.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct ErrOrUsize(usize);
+
+impl ErrOrUsize {
+	/// The largest value that can be stored in an `ErrOrUsize`.
+	pub const MAX_VALUE: usize = usize::MAX - MAX_ERRNO;
+
+	/// Creates an `ErrOrUsize` containing a value. If the value is greater
+	/// than [`MAX_VALUE`](ErrOrUsize::MAX_VALUE), returns `None`.
+	#[inline]
+	pub const fn new(value: usize) -> Option<ErrOrUsize> {
+		if is_err_value(value) {
+			return None;
+		}
+		Some(ErrOrUsize(value))
+	}
+
+	/// Creates an `ErrOrUsize` containing an error number.
+	#[inline]
+	pub const fn from_err(err: Error) -> ErrOrUsize {
+		ErrOrUsize(err_value(err))
+	}
+
+	/// Creates an `ErrOrUsize` from its encoded representation.
+	#[inline]
+	pub const fn from_raw(raw: usize) -> ErrOrUsize {
+		ErrOrUsize(raw)
+	}
+
+	/// Returns the encoded representation of this `ErrOrUsize`.
+	#[inline]
+	pub const fn into_raw(self) -> usize {
+		self.0
+	}
+
+	/// Returns `true` if this `ErrOrUsize` contains an error number.
+	#[inline]
+	pub const fn is_err(self) -> bool {
+		is_err_value(self.0)
+	}
+
+	/// Returns the value, or `None` if this `ErrOrUsize` contains an error
+	/// number.
+	#[inline]
+	pub const fn value(self) -> Option<usize> {
+		if is_err_value(self.0) {
+			return None;
+		}
+		Some(self.0)
+	}
+
+	/// Returns the error number, or `None` if this `ErrOrUsize` contains a
+	/// value.
+	#[inline]
+	pub const fn err(self) -> Option<Error> {
+		raw_err(self.0)
+	}
+
+	/// Converts this `ErrOrUsize` to a `Result`.
+	#[inline]
+	pub const fn into_result(self) -> Result<usize, Error> {
+		match raw_err(self.0) {
+			Some(err) => Err(err),
+			None => Ok(self.0),
+		}
+	}
+}
+
+impl fmt::Debug for ErrOrUsize {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.into_result() {
+			Ok(value) => f.debug_tuple("Ok").field(&value).finish(),
+			Err(err) => f.debug_tuple("Err").field(&err).finish(),
+		}
+	}
+}
+
+impl From<Error> for ErrOrUsize {
+	#[inline]
+	fn from(err: Error) -> ErrOrUsize {
+		ErrOrUsize::from_err(err)
+	}
+}
+
+impl From<ErrOrUsize> for Result<usize, Error> {
+	#[inline]
+	fn from(packed: ErrOrUsize) -> Result<usize, Error> {
+		packed.into_result()
+	}
+}
+
+impl TryFrom<Result<usize, Error>> for ErrOrUsize {
+	/// The value that is too large to be stored in an `ErrOrUsize`.
+	type Error = usize;
+
+	#[inline]
+	fn try_from(result: Result<usize, Error>) -> Result<ErrOrUsize, usize> {
+		match result {
+			Ok(value) => ErrOrUsize::new(value).ok_or(value),
+			Err(err) => Ok(ErrOrUsize::from_err(err)),
+		}
+	}
+}
+
+/// A pointer or an error number, packed into a single pointer.
+///
+/// This is the encoding used by the Linux kernel's `ERR_PTR()`, `IS_ERR()`,
+/// and `PTR_ERR()`: pointers to the top 4095 bytes of the address space are
+/// the negated error numbers `-4095 ..= -1`, and every other pointer
+/// (including null) is a successful result.
+///
+/// Unlike `Result<*mut T, Error>`, an `ErrOrPtr<T>` has the same size and ABI
+/// as a `*mut T`.
+#[repr(transparent)]
+pub struct ErrOrPtr<T>(*mut T);
+
+impl<T> ErrOrPtr<T> {
+	/// Creates an `ErrOrPtr` containing a pointer. If the pointer is within
+	/// the top 4095 bytes of the address space, returns `None`.
+	#[inline]
+	pub fn new(ptr: *mut T) -> Option<ErrOrPtr<T>> {
+		if is_err_value(ptr as usize) {
+			return None;
+		}
+		Some(ErrOrPtr(ptr))
+	}
+
+	/// Creates an `ErrOrPtr` containing an error number.
+	#[inline]
+	pub fn from_err(err: Error) -> ErrOrPtr<T> {
+		ErrOrPtr(err_value(err) as *mut T)
+	}
+
+	/// Creates an `ErrOrPtr` from its encoded representation.
+	#[inline]
+	pub const fn from_raw(raw: *mut T) -> ErrOrPtr<T> {
+		ErrOrPtr(raw)
+	}
+
+	/// Returns the encoded representation of this `ErrOrPtr`.
+	#[inline]
+	pub const fn into_raw(self) -> *mut T {
+		self.0
+	}
+
+	/// Returns `true` if this `ErrOrPtr` contains an error number.
+	#[inline]
+	pub fn is_err(self) -> bool {
+		is_err_value(self.0 as usize)
+	}
+
+	/// Returns the pointer, or `None` if this `ErrOrPtr` contains an error
+	/// number.
+	#[inline]
+	pub fn ptr(self) -> Option<*mut T> {
+		if self.is_err() {
+			return None;
+		}
+		Some(self.0)
+	}
+
+	/// Returns the error number, or `None` if this `ErrOrPtr` contains a
+	/// pointer.
+	#[inline]
+	pub fn err(self) -> Option<Error> {
+		raw_err(self.0 as usize)
+	}
+
+	/// Converts this `ErrOrPtr` to a `Result`.
+	#[inline]
+	pub fn into_result(self) -> Result<*mut T, Error> {
+		match self.err() {
+			Some(err) => Err(err),
+			None => Ok(self.0),
+		}
+	}
+}
+
+impl<T> Clone for ErrOrPtr<T> {
+	#[inline]
+	fn clone(&self) -> ErrOrPtr<T> {
+		*self
+	}
+}
+
+impl<T> Copy for ErrOrPtr<T> {}
+
+impl<T> PartialEq for ErrOrPtr<T> {
+	#[inline]
+	fn eq(&self, other: &ErrOrPtr<T>) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<T> Eq for ErrOrPtr<T> {}
+
+impl<T> hash::Hash for ErrOrPtr<T> {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.0.hash(state)
+	}
+}
+
+impl<T> fmt::Debug for ErrOrPtr<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.into_result() {
+			Ok(ptr) => f.debug_tuple("Ok").field(&ptr).finish(),
+			Err(err) => f.debug_tuple("Err").field(&err).finish(),
+		}
+	}
+}
+
+impl<T> From<Error> for ErrOrPtr<T> {
+	#[inline]
+	fn from(err: Error) -> ErrOrPtr<T> {
+		ErrOrPtr::from_err(err)
+	}
+}
+
+impl<T> From<ErrOrPtr<T>> for Result<*mut T, Error> {
+	#[inline]
+	fn from(packed: ErrOrPtr<T>) -> Result<*mut T, Error> {
+		packed.into_result()
+	}
+}
+
+impl<T> TryFrom<Result<*mut T, Error>> for ErrOrPtr<T> {
+	/// The pointer that is too close to the end of the address space to be
+	/// stored in an `ErrOrPtr`.
+	type Error = *mut T;
+
+	#[inline]
+	fn try_from(
+		result: Result<*mut T, Error>,
+	) -> Result<ErrOrPtr<T>, *mut T> {
+		match result {
+			Ok(ptr) => ErrOrPtr::new(ptr).ok_or(ptr),
+			Err(err) => Ok(ErrOrPtr::from_err(err)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::ptr;
+
+	use super::*;
+	use crate::target;
+
+	#[test]
+	fn usize_boundaries() {
+		let packed = ErrOrUsize::from_raw(usize::MAX);
+		assert_eq!(packed.err(), Error::new(1));
+		assert_eq!(packed.value(), None);
+
+		let packed = ErrOrUsize::from_raw(usize::MAX - 4094);
+		assert!(packed.is_err());
+		assert_eq!(packed.err(), Error::new(4095));
+		assert_eq!(packed.into_result(), Err(Error::new(4095).unwrap()));
+
+		let packed = ErrOrUsize::from_raw(usize::MAX - 4095);
+		assert!(!packed.is_err());
+		assert_eq!(packed.err(), None);
+		assert_eq!(packed.value(), Some(ErrOrUsize::MAX_VALUE));
+
+		let packed = ErrOrUsize::from_raw(0);
+		assert_eq!(packed.into_result(), Ok(0));
+	}
+
+	#[test]
+	fn usize_new() {
+		let max = ErrOrUsize::MAX_VALUE;
+		let packed = ErrOrUsize::new(max).unwrap();
+		assert_eq!(packed.into_raw(), max);
+		assert_eq!(packed.value(), Some(max));
+		assert_eq!(ErrOrUsize::new(max + 1), None);
+		assert_eq!(ErrOrUsize::new(usize::MAX), None);
+	}
+
+	#[test]
+	fn usize_errors() {
+		for err in (1..=0xFFF).filter_map(Error::new) {
+			let packed = ErrOrUsize::from_err(err);
+			assert_eq!(packed.into_raw(), (-(err.get() as isize)) as usize);
+			assert_eq!(packed.err(), Some(err));
+			assert_eq!(ErrOrUsize::from_raw(packed.into_raw()), packed);
+		}
+	}
+
+	#[test]
+	fn usize_result() {
+		let max = ErrOrUsize::MAX_VALUE;
+		assert_eq!(
+			ErrOrUsize::try_from(Ok(max)).map(Result::from),
+			Ok(Ok(max)),
+		);
+		assert_eq!(ErrOrUsize::try_from(Ok(max + 1)), Err(max + 1));
+		assert_eq!(
+			ErrOrUsize::try_from(Err(target::ENOENT)).map(Result::from),
+			Ok(Err(target::ENOENT)),
+		);
+		assert_eq!(
+			Result::from(ErrOrUsize::from(target::ENOENT)),
+			Err(target::ENOENT),
+		);
+	}
+
+	#[test]
+	fn ptr_null_and_dangling() {
+		let null = ErrOrPtr::<u32>::new(ptr::null_mut()).unwrap();
+		assert!(!null.is_err());
+		assert_eq!(null.ptr(), Some(ptr::null_mut()));
+		assert_eq!(null.err(), None);
+
+		let dangling = ptr::NonNull::<u32>::dangling().as_ptr();
+		let packed = ErrOrPtr::new(dangling).unwrap();
+		assert_eq!(packed.into_result(), Ok(dangling));
+
+		let top = (usize::MAX - 4094) as *mut u32;
+		assert!(ErrOrPtr::new(top).is_none());
+		assert_eq!(ErrOrPtr::from_raw(top).err(), Error::new(4095));
+		let below = (usize::MAX - 4095) as *mut u32;
+		assert_eq!(ErrOrPtr::from_raw(below).ptr(), Some(below));
+	}
+
+	#[test]
+	fn ptr_result() {
+		let mut value = 0u32;
+		let valid: *mut u32 = &mut value;
+		assert_eq!(
+			ErrOrPtr::try_from(Ok(valid)).map(Result::from),
+			Ok(Ok(valid)),
+		);
+		let top = usize::MAX as *mut u32;
+		assert_eq!(ErrOrPtr::try_from(Ok(top)), Err(top));
+		assert_eq!(
+			ErrOrPtr::<u32>::try_from(Err(target::EFAULT)).map(Result::from),
+			Ok(Err(target::EFAULT)),
+		);
+		assert_eq!(
+			Result::from(ErrOrPtr::<u32>::from(target::EFAULT)),
+			Err(target::EFAULT),
+		);
+	}
+}
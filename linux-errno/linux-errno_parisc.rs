@@ -292,14 +292,13 @@ errno_constants! {
 	EHWPOISON = 257,
 }
 
-/// SuSv3 and Solaris wants one 'L' (alias for [ECANCELLED])
-pub const ECANCELED: crate::Error = ECANCELLED;
-
-/// Alias for [EDEADLK]
-pub const EDEADLOCK: crate::Error = EDEADLK;
-
-/// For HP's NFS apparently (alias for [ECONNREFUSED])
-pub const EREFUSED: crate::Error = ECONNREFUSED;
-
-/// Operation would block (Not HPUX compliant) (alias for [ECANCELLED])
-pub const EWOULDBLOCK: crate::Error = EAGAIN;
+errno_aliases! {
+	/// SuSv3 and Solaris wants one 'L' (alias for [ECANCELLED])
+	ECANCELED = ECANCELLED,
+	/// Alias for [EDEADLK]
+	EDEADLOCK = EDEADLK,
+	/// For HP's NFS apparently (alias for [ECONNREFUSED])
+	EREFUSED = ECONNREFUSED,
+	/// Operation would block (Not HPUX compliant) (alias for [ECANCELLED])
+	EWOULDBLOCK = EAGAIN,
+}
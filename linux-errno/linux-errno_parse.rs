@@ -0,0 +1,363 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Extracting error numbers from `strace`, `auditd`, and kernel log lines.
+//!
+//! The [`errors`] function scans a line of text for error numbers in the
+//! following formats:
+//!
+//! | Format             | Example                                           |
+//! |--------------------|---------------------------------------------------|
+//! | [`Format::Strace`] | `= -1 ENOENT (No such file or directory)`         |
+//! | [`Format::Audit`]  | `exit=-13` or `exit=EACCES(Permission denied)`    |
+//! | [`Format::Kernel`] | `error -5`, `error=-5`, `err -5`, or `error -EIO` |
+//!
+//! Error numbers and names are resolved using the numbering of an [`Arch`],
+//! which should be the architecture of the host that produced the log. Use
+//! [`Arch::translate`] to convert the resolved errors to another
+//! architecture.
+//!
+//! The kernel-internal error numbers in [`kernel`], such as
+//! the `ERESTARTSYS` reported by `strace` for interrupted system calls, are
+//! also recognized.
+
+use core::ops::Range;
+
+use crate::Error;
+use crate::arch::Arch;
+use crate::kernel;
+
+/// The log format of an error number found by [`errors`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+	/// A system call result printed by `strace`, such as
+	/// `= -1 ENOENT (No such file or directory)`.
+	Strace,
+	/// The `exit` field of an `auditd` `SYSCALL` record, such as `exit=-13`.
+	Audit,
+	/// An error code in a kernel log message, such as `error -5`.
+	Kernel,
+}
+
+/// An error number found by [`errors`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Match {
+	/// The format in which the error number was found.
+	pub format: Format,
+	/// The byte range of the error number or name within the line.
+	pub span: Range<usize>,
+	/// The error number, in the numbering of the [`Arch`] passed to
+	/// [`errors`].
+	pub error: Error,
+	/// The name of the error number, or `None` if the error number is not
+	/// defined.
+	pub name: Option<&'static str>,
+}
+
+/// Returns an iterator over the error numbers in a line of text, in order
+/// of their position in the line.
+pub fn errors(line: &[u8], arch: Arch) -> Errors<'_> {
+	Errors { line, arch, pos: 0 }
+}
+
+/// Iterator returned by [`errors`].
+#[derive(Clone, Debug)]
+pub struct Errors<'a> {
+	line: &'a [u8],
+	arch: Arch,
+	pos: usize,
+}
+
+impl Iterator for Errors<'_> {
+	type Item = Match;
+
+	fn next(&mut self) -> Option<Match> {
+		while self.pos < self.line.len() {
+			let start = self.pos;
+			self.pos += 1;
+			if let Some(m) = self.match_at(start) {
+				self.pos = m.span.end;
+				return Some(m);
+			}
+		}
+		None
+	}
+}
+
+impl Errors<'_> {
+	fn match_at(&self, start: usize) -> Option<Match> {
+		let line = self.line;
+		let rest = &line[start..];
+		if rest.starts_with(b"= -1 ") {
+			return self.name_at(Format::Strace, start + 5);
+		}
+		if rest.starts_with(b"= ? ") {
+			return self.name_at(Format::Strace, start + 4);
+		}
+		if !at_word_start(line, start) {
+			return None;
+		}
+		if rest.starts_with(b"exit=") {
+			let pos = start + 5;
+			return match line.get(pos) {
+				Some(b'-') => self.negated_at(Format::Audit, pos),
+				_ => self.name_at(Format::Audit, pos),
+			};
+		}
+		let prefix_len = if rest.starts_with(b"error") {
+			5
+		} else if rest.starts_with(b"err") {
+			3
+		} else {
+			return None;
+		};
+		let pos = start + prefix_len;
+		match line.get(pos) {
+			Some(b' ') | Some(b'=') => {},
+			_ => return None,
+		}
+		if line.get(pos + 1) != Some(&b'-') {
+			return None;
+		}
+		self.negated_at(Format::Kernel, pos + 1)
+			.or_else(|| self.name_at(Format::Kernel, pos + 2))
+	}
+
+	// Parses a negated decimal error number, such as `-13`.
+	fn negated_at(&self, format: Format, start: usize) -> Option<Match> {
+		let end = token_end(self.line, start + 1, u8::is_ascii_digit);
+		let digits = &self.line[start + 1..end];
+		if digits.is_empty() || digits.len() > 4 {
+			return None;
+		}
+		if !at_word_end(self.line, end) {
+			return None;
+		}
+		let errno = digits
+			.iter()
+			.fold(0u16, |acc, &b| acc * 10 + u16::from(b - b'0'));
+		let error = Error::new(errno)?;
+		Some(Match {
+			format,
+			span: start..end,
+			error,
+			name: self.err_name(error),
+		})
+	}
+
+	// Parses an error name, such as `ENOENT`.
+	fn name_at(&self, format: Format, start: usize) -> Option<Match> {
+		let end = token_end(self.line, start, |&b| {
+			b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_'
+		});
+		if self.line.get(start) != Some(&b'E') {
+			return None;
+		}
+		if !at_word_end(self.line, end) {
+			return None;
+		}
+		let name = core::str::from_utf8(&self.line[start..end]).ok()?;
+		let (name, error) = self.err_from_name(name)?;
+		Some(Match {
+			format,
+			span: start..end,
+			error,
+			name: Some(name),
+		})
+	}
+
+	fn err_name(&self, err: Error) -> Option<&'static str> {
+		self.arch.err_name(err).or_else(|| kernel::err_name(err))
+	}
+
+	fn err_from_name(&self, name: &str) -> Option<(&'static str, Error)> {
+		self.arch
+			.entries()
+			.chain(kernel::ERRNOS.iter())
+			.find(|entry| entry.name == name)
+			.map(|entry| (entry.name, entry.error))
+	}
+}
+
+fn token_end(line: &[u8], start: usize, f: impl Fn(&u8) -> bool) -> usize {
+	match line.get(start..) {
+		Some(rest) => start + rest.iter().take_while(|b| f(b)).count(),
+		None => start,
+	}
+}
+
+fn at_word_start(line: &[u8], pos: usize) -> bool {
+	pos == 0 || !is_word_byte(line[pos - 1])
+}
+
+fn at_word_end(line: &[u8], pos: usize) -> bool {
+	match line.get(pos) {
+		Some(&b) => !is_word_byte(b),
+		None => true,
+	}
+}
+
+fn is_word_byte(b: u8) -> bool {
+	b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(line: &str, arch: Arch) -> Option<Match> {
+		let mut iter = errors(line.as_bytes(), arch);
+		let m = iter.next();
+		assert_eq!(iter.next(), None, "{:?}", line);
+		m
+	}
+
+	fn err(errno: u16) -> Error {
+		Error::new(errno).unwrap()
+	}
+
+	#[test]
+	fn strace() {
+		let line = "openat(AT_FDCWD, \"/x\", O_RDONLY) = -1 ENOENT (No such \
+		            file or directory)";
+		assert_eq!(
+			parse(line, Arch::Generic),
+			Some(Match {
+				format: Format::Strace,
+				span: 38..44,
+				error: err(2),
+				name: Some("ENOENT"),
+			}),
+		);
+		assert_eq!(&line[38..44], "ENOENT");
+
+		let line = "read(0, ...) = ? ERESTARTSYS (To be restarted)";
+		assert_eq!(
+			parse(line, Arch::Generic),
+			Some(Match {
+				format: Format::Strace,
+				span: 17..28,
+				error: kernel::ERESTARTSYS,
+				name: Some("ERESTARTSYS"),
+			}),
+		);
+	}
+
+	#[test]
+	fn audit() {
+		let line = "type=SYSCALL syscall=257 success=no exit=-13 a0=ffffff9c";
+		assert_eq!(
+			parse(line, Arch::Generic),
+			Some(Match {
+				format: Format::Audit,
+				span: 41..44,
+				error: err(13),
+				name: Some("EACCES"),
+			}),
+		);
+		assert_eq!(&line[41..44], "-13");
+
+		let line = "success=no exit=EACCES(Permission denied)";
+		assert_eq!(
+			parse(line, Arch::Generic),
+			Some(Match {
+				format: Format::Audit,
+				span: 16..22,
+				error: err(13),
+				name: Some("EACCES"),
+			}),
+		);
+	}
+
+	#[test]
+	fn kernel() {
+		let cases: &[(&str, Range<usize>)] = &[
+			("Buffer I/O error -5 on dev sda", 17..19),
+			("xfs: error=-5 reading block", 11..13),
+			("nfs: err -5", 9..11),
+			("ext4: error -EIO", 13..16),
+		];
+		for (line, span) in cases {
+			let m = parse(line, Arch::Generic).unwrap();
+			assert_eq!(m.format, Format::Kernel, "{:?}", line);
+			assert_eq!(&m.span, span, "{:?}", line);
+			assert_eq!(m.error, err(5), "{:?}", line);
+			assert_eq!(m.name, Some("EIO"), "{:?}", line);
+		}
+	}
+
+	#[test]
+	fn multiple() {
+		let line = "error -5 then error -ENOSPC";
+		let matches: [Option<Match>; 3] = {
+			let mut iter = errors(line.as_bytes(), Arch::Generic);
+			[iter.next(), iter.next(), iter.next()]
+		};
+		assert_eq!(matches[0].as_ref().map(|m| m.span.clone()), Some(6..8));
+		assert_eq!(matches[1].as_ref().map(|m| m.span.clone()), Some(21..27));
+		assert_eq!(matches[1].as_ref().map(|m| m.error), Some(err(28)));
+		assert_eq!(matches[2], None);
+	}
+
+	#[test]
+	fn arch() {
+		let line = "success=no exit=-1133";
+		let m = parse(line, Arch::Mips).unwrap();
+		assert_eq!(m.error, err(1133));
+		assert_eq!(m.name, Some("EDQUOT"));
+
+		let m = parse(line, Arch::Generic).unwrap();
+		assert_eq!(m.error, err(1133));
+		assert_eq!(m.name, None);
+
+		let m = parse("= -1 EDQUOT (Quota exceeded)", Arch::Mips).unwrap();
+		assert_eq!(m.error, err(1133));
+		let m = parse("= -1 EDQUOT (Quota exceeded)", Arch::Generic).unwrap();
+		assert_eq!(m.error, err(122));
+
+		let m = parse("= -1 EDEADLOCK (x)", Arch::Powerpc).unwrap();
+		assert_eq!(m.error, err(58));
+		let m = parse("= -1 EDEADLOCK (x)", Arch::X86).unwrap();
+		assert_eq!(m.error, err(35));
+		assert_eq!(m.name, Some("EDEADLOCK"));
+	}
+
+	#[test]
+	fn no_match() {
+		let lines = [
+			"read(3, \"\", 4096) = -1",
+			"read(3, \"\", 4096) = -1 ",
+			"read(3, \"\", 4096) = 0",
+			"= -1 EFOO (Unknown)",
+			"= -1 enoent",
+			"success=yes exit=0",
+			"exit=-0",
+			"exit=-4096",
+			"exit=EFOO",
+			"exit=-13x",
+			"preexit=-13",
+			"error -99999",
+			"error 5",
+			"error -",
+			"error -EFOO",
+			"terror -5",
+			"errors -5",
+			"error -5x",
+		];
+		for line in lines.iter() {
+			assert_eq!(parse(line, Arch::Generic), None, "{:?}", line);
+		}
+	}
+}
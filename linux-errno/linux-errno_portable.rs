@@ -0,0 +1,133 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Error numbers that are the same on every architecture.
+//!
+//! This module contains only the error numbers that have the same value in
+//! every [`arch`](crate::arch) module, so code written against it is correct
+//! for error numbers from any architecture without translation. Like the
+//! [`Arch`](crate::arch::Arch) tables, it is available on all targets.
+//!
+//! The portable error numbers are the original Unix error numbers `EPERM`
+//! (1) through `ERANGE` (34), except for `EAGAIN`. Error numbers such as
+//! `EAGAIN`, `EDEADLK`, and `ENOTEMPTY` are not portable, because their
+//! values differ on `alpha`, `mips`, `parisc`, or `sparc`.
+
+pub use crate::arch_generic::{
+	E2BIG,
+	EACCES,
+	EBADF,
+	EBUSY,
+	ECHILD,
+	EDOM,
+	EEXIST,
+	EFAULT,
+	EFBIG,
+	EINTR,
+	EINVAL,
+	EIO,
+	EISDIR,
+	EMFILE,
+	EMLINK,
+	ENFILE,
+	ENODEV,
+	ENOENT,
+	ENOEXEC,
+	ENOMEM,
+	ENOSPC,
+	ENOTBLK,
+	ENOTDIR,
+	ENOTTY,
+	ENXIO,
+	EPERM,
+	EPIPE,
+	ERANGE,
+	EROFS,
+	ESPIPE,
+	ESRCH,
+	ETXTBSY,
+	EXDEV,
+};
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::arch::Arch;
+	use crate::Error;
+
+	macro_rules! portable {
+		( $( $name:ident , )* ) => {
+			&[ $( (stringify!($name), $name), )* ]
+		};
+	}
+
+	const PORTABLE: &[(&str, Error)] = portable! {
+		E2BIG,
+		EACCES,
+		EBADF,
+		EBUSY,
+		ECHILD,
+		EDOM,
+		EEXIST,
+		EFAULT,
+		EFBIG,
+		EINTR,
+		EINVAL,
+		EIO,
+		EISDIR,
+		EMFILE,
+		EMLINK,
+		ENFILE,
+		ENODEV,
+		ENOENT,
+		ENOEXEC,
+		ENOMEM,
+		ENOSPC,
+		ENOTBLK,
+		ENOTDIR,
+		ENOTTY,
+		ENXIO,
+		EPERM,
+		EPIPE,
+		ERANGE,
+		EROFS,
+		ESPIPE,
+		ESRCH,
+		ETXTBSY,
+		EXDEV,
+	};
+
+	#[test]
+	fn same_on_every_arch() {
+		for &(name, err) in PORTABLE {
+			for &arch in Arch::ALL.iter() {
+				assert_eq!(arch.err_from_name(name), Some(err), "{}", name);
+				assert_eq!(arch.err_name(err), Some(name), "{}", name);
+			}
+		}
+	}
+
+	#[test]
+	fn complete() {
+		// Every name that has the same number on every arch is portable.
+		for entry in crate::arch_generic::ERRNOS {
+			let same = Arch::ALL.iter().all(|arch| {
+				arch.err_from_name(entry.name) == Some(entry.error)
+			});
+			let portable = PORTABLE.iter().any(|&(name, _)| name == entry.name);
+			assert_eq!(same, portable, "{}", entry.name);
+		}
+	}
+}
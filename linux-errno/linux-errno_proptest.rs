@@ -0,0 +1,121 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+use core::ops::RangeInclusive;
+
+use ::proptest::arbitrary::{any, Arbitrary};
+use ::proptest::prop_oneof;
+use ::proptest::sample::{select, Select};
+use ::proptest::strategy::{Map, Strategy};
+
+use crate::arch::Arch;
+use crate::{ErrnoName, Error};
+
+/// Generates error numbers uniformly over the valid range `[1, 4096)`.
+impl Arbitrary for Error {
+	type Parameters = ();
+	type Strategy = Map<RangeInclusive<u16>, fn(u16) -> Error>;
+
+	fn arbitrary_with(_args: ()) -> Self::Strategy {
+		(1..=0xFFF).prop_map(|errno| unsafe { Error::new_unchecked(errno) })
+	}
+}
+
+impl Arbitrary for Arch {
+	type Parameters = ();
+	type Strategy = Select<Arch>;
+
+	fn arbitrary_with(_args: ()) -> Self::Strategy {
+		const ALL: &[Arch] = &Arch::ALL;
+		select(ALL)
+	}
+}
+
+impl Arbitrary for ErrnoName {
+	type Parameters = ();
+	type Strategy = Select<ErrnoName>;
+
+	fn arbitrary_with(_args: ()) -> Self::Strategy {
+		select(ErrnoName::ALL)
+	}
+}
+
+impl Arch {
+	/// Returns a `proptest` strategy for error numbers, weighted toward the
+	/// error numbers defined on this architecture.
+	///
+	/// Seven in eight generated errors are defined on this architecture, with
+	/// each defined error number equally likely (aliases such as `EWOULDBLOCK`
+	/// are not counted separately). The rest are uniform over the valid range
+	/// `[1, 4096)`, so that code under test also sees unknown error numbers.
+	/// Shrinking moves toward the lowest defined error number.
+	pub fn error_strategy(self) -> impl Strategy<Value = Error> {
+		let errnos = self.errnos();
+		let known = (0..errnos.len()).prop_map(move |idx| errnos[idx].error);
+		prop_oneof![
+			7 => known,
+			1 => any::<Error>(),
+		]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ::proptest::strategy::ValueTree;
+	use ::proptest::test_runner::TestRunner;
+
+	use super::*;
+
+	fn sample<S: Strategy>(strategy: S, runner: &mut TestRunner) -> S::Value {
+		strategy.new_tree(runner).unwrap().current()
+	}
+
+	#[test]
+	fn error() {
+		let mut runner = TestRunner::deterministic();
+		for _ in 0..1000 {
+			let err = sample(any::<Error>(), &mut runner);
+			assert!(err.get() >= 1 && err.get() <= 0xFFF);
+		}
+	}
+
+	#[test]
+	fn error_strategy() {
+		let mut runner = TestRunner::deterministic();
+		for &arch in Arch::ALL.iter() {
+			let mut known = 0;
+			for _ in 0..1000 {
+				let err = sample(arch.error_strategy(), &mut runner);
+				if arch.err_name(err).is_some() {
+					known += 1;
+				}
+			}
+			assert!(known > 750, "{:?}: {}", arch, known);
+		}
+	}
+
+	#[test]
+	fn shrink_to_lowest() {
+		let mut runner = TestRunner::deterministic();
+		let strategy = Arch::Mips.error_strategy();
+		for _ in 0..100 {
+			let mut tree = strategy.new_tree(&mut runner).unwrap();
+			while tree.simplify() {}
+			let err = tree.current();
+			let lowest = Arch::Mips.errnos()[0].error;
+			assert!(err == lowest || Arch::Mips.err_name(err).is_none());
+		}
+	}
+}
@@ -304,5 +304,7 @@ errno_constants! {
 	EHWPOISON = 135,
 }
 
-/// Operation would block (alias for [EAGAIN])
-pub const EWOULDBLOCK: crate::Error = EAGAIN;
+errno_aliases! {
+	/// Operation would block (alias for [EAGAIN])
+	EWOULDBLOCK = EAGAIN,
+}
@@ -0,0 +1,712 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Conversions from Linux error numbers to HTTP status codes, gRPC status
+//! codes, and `sysexits.h` exit codes.
+//!
+//! The conversions are methods of [`Error`], plus the [`Exit`] type for
+//! returning an error from `main`. Error numbers are interpreted according
+//! to the current target architecture.
+
+#[cfg(feature = "std")]
+use std::process;
+
+use crate::Error;
+use crate::target;
+
+// https://github.com/grpc/grpc/blob/master/doc/statuscodes.md
+
+const GRPC_CANCELLED: i32 = 1;
+const GRPC_UNKNOWN: i32 = 2;
+const GRPC_INVALID_ARGUMENT: i32 = 3;
+const GRPC_DEADLINE_EXCEEDED: i32 = 4;
+const GRPC_NOT_FOUND: i32 = 5;
+const GRPC_ALREADY_EXISTS: i32 = 6;
+const GRPC_PERMISSION_DENIED: i32 = 7;
+const GRPC_RESOURCE_EXHAUSTED: i32 = 8;
+const GRPC_FAILED_PRECONDITION: i32 = 9;
+const GRPC_ABORTED: i32 = 10;
+const GRPC_OUT_OF_RANGE: i32 = 11;
+const GRPC_UNIMPLEMENTED: i32 = 12;
+const GRPC_UNAVAILABLE: i32 = 14;
+
+// https://man.freebsd.org/cgi/man.cgi?query=sysexits
+
+const EX_DATAERR: u8 = 65;
+const EX_NOINPUT: u8 = 66;
+const EX_NOHOST: u8 = 68;
+const EX_UNAVAILABLE: u8 = 69;
+const EX_SOFTWARE: u8 = 70;
+const EX_OSERR: u8 = 71;
+const EX_CANTCREAT: u8 = 73;
+const EX_IOERR: u8 = 74;
+const EX_TEMPFAIL: u8 = 75;
+const EX_PROTOCOL: u8 = 76;
+const EX_NOPERM: u8 = 77;
+
+impl Error {
+	/// Returns the HTTP status code that best describes this error.
+	///
+	/// This is intended for services that report failed system calls in their
+	/// API responses, such as file and object storage gateways. Errors that
+	/// are not listed below are reported as `500 Internal Server Error`.
+	///
+	/// * `400 Bad Request`: `EINVAL`, `EDESTADDRREQ`, `EDOM`, `EILSEQ`,
+	///   `ENAMETOOLONG`, `ENOPROTOOPT`, `ENOSTR`, `ENOTSOCK`, `ENOTTY`,
+	///   `EPROTOTYPE`, `ESPIPE`, `ERANGE`, `EOVERFLOW`, `ECHRNG`, `EBADMSG`
+	/// * `401 Unauthorized`: `EKEYEXPIRED`, `EKEYREVOKED`, `EKEYREJECTED`
+	/// * `403 Forbidden`: `EPERM`, `EACCES`, `EROFS`, `ENOKEY`
+	/// * `404 Not Found`: `ENOENT`, `ENODEV`, `ENOMEDIUM`, `ENXIO`, `ESRCH`
+	/// * `409 Conflict`: `EEXIST`, `ENOTEMPTY`, `ENOTDIR`, `EISDIR`, `EBUSY`,
+	///   `ETXTBSY`, `EALREADY`, `EISCONN`, `EDEADLK`, `EADDRINUSE`,
+	///   `EADDRNOTAVAIL`, `ENOTUNIQ`
+	/// * `410 Gone`: `ESTALE`, `EIDRM`
+	/// * `413 Content Too Large`: `E2BIG`, `EFBIG`, `EMSGSIZE`
+	/// * `499 Client Closed Request`: `ECANCELED`
+	/// * `501 Not Implemented`: `ENOSYS`, `EOPNOTSUPP`, `EAFNOSUPPORT`,
+	///   `EPFNOSUPPORT`, `EPROTONOSUPPORT`, `ESOCKTNOSUPPORT`, `ENOPKG`,
+	///   `EXDEV`
+	/// * `502 Bad Gateway`: `ECONNREFUSED`, `ECONNRESET`, `ECONNABORTED`,
+	///   `EHOSTUNREACH`, `ENETUNREACH`, `EPROTO`, `EREMOTEIO`, `ENOLINK`,
+	///   `ECOMM`
+	/// * `503 Service Unavailable`: `EAGAIN`, `EINTR`, `EHOSTDOWN`, `ENETDOWN`,
+	///   `ENETRESET`, `ENONET`, `ENOMEM`, `ENOBUFS`, `ENOSR`, `EMFILE`,
+	///   `ENFILE`, `EUSERS`, `ENOLCK`
+	/// * `504 Gateway Timeout`: `ETIMEDOUT`, `ETIME`
+	/// * `507 Insufficient Storage`: `ENOSPC`, `EDQUOT`, `EMLINK`
+	/// * `508 Loop Detected`: `ELOOP`
+	/// * `500 Internal Server Error`: `EADV`, `EBADE`, `EBADF`, `EBADFD`,
+	///   `EBADR`, `EBADRQC`, `EBADSLT`, `EBFONT`, `ECHILD`, `EDEADLOCK`,
+	///   `EDOTDOT`, `EFAULT`, `EHWPOISON`, `EINIT`, `EINPROGRESS`, `EIO`,
+	///   `EISNAM`, `EL2HLT`, `EL2NSYNC`, `EL3HLT`, `EL3RST`, `ELIBACC`,
+	///   `ELIBBAD`, `ELIBEXEC`, `ELIBMAX`, `ELIBSCN`, `ELNRNG`, `EMEDIUMTYPE`,
+	///   `EMULTIHOP`, `ENAVAIL`, `ENOANO`, `ENOCSI`, `ENODATA`, `ENOEXEC`,
+	///   `ENOMSG`, `ENOSYM`, `ENOTBLK`, `ENOTCONN`, `ENOTNAM`,
+	///   `ENOTRECOVERABLE`, `EOWNERDEAD`, `EPIPE`, `EPROCLIM`, `EREMCHG`,
+	///   `EREMDEV`, `EREMOTE`, `EREMOTERELEASE`, `ERESTART`, `ERFKILL`,
+	///   `ERREMOTE`, `ESHUTDOWN`, `ESRMNT`, `ESTRPIPE`, `ETOOMANYREFS`,
+	///   `EUCLEAN`, `EUNATCH`, `EXFULL`
+	///
+	/// Aliases such as `EWOULDBLOCK` have the same status code as the error
+	/// they alias. On architectures where `EDEADLOCK` is distinct from
+	/// `EDEADLK`, it is reported as `500 Internal Server Error`.
+	pub const fn to_http_status(&self) -> u16 {
+		match *self {
+			target::EINVAL          => 400,
+			target::EDESTADDRREQ    => 400,
+			target::EDOM            => 400,
+			target::EILSEQ          => 400,
+			target::ENAMETOOLONG    => 400,
+			target::ENOPROTOOPT     => 400,
+			target::ENOSTR          => 400,
+			target::ENOTSOCK        => 400,
+			target::ENOTTY          => 400,
+			target::EPROTOTYPE      => 400,
+			target::ESPIPE          => 400,
+			target::ERANGE          => 400,
+			target::EOVERFLOW       => 400,
+			target::ECHRNG          => 400,
+			target::EBADMSG         => 400,
+			target::EKEYEXPIRED     => 401,
+			target::EKEYREVOKED     => 401,
+			target::EKEYREJECTED    => 401,
+			target::EPERM           => 403,
+			target::EACCES          => 403,
+			target::EROFS           => 403,
+			target::ENOKEY          => 403,
+			target::ENOENT          => 404,
+			target::ENODEV          => 404,
+			target::ENOMEDIUM       => 404,
+			target::ENXIO           => 404,
+			target::ESRCH           => 404,
+			target::EEXIST          => 409,
+			target::ENOTEMPTY       => 409,
+			target::ENOTDIR         => 409,
+			target::EISDIR          => 409,
+			target::EBUSY           => 409,
+			target::ETXTBSY         => 409,
+			target::EALREADY        => 409,
+			target::EISCONN         => 409,
+			target::EDEADLK         => 409,
+			target::EADDRINUSE      => 409,
+			target::EADDRNOTAVAIL   => 409,
+			target::ENOTUNIQ        => 409,
+			target::ESTALE          => 410,
+			target::EIDRM           => 410,
+			target::E2BIG           => 413,
+			target::EFBIG           => 413,
+			target::EMSGSIZE        => 413,
+			target::ECANCELED       => 499,
+			target::ENOSYS          => 501,
+			target::EOPNOTSUPP      => 501,
+			target::EAFNOSUPPORT    => 501,
+			target::EPFNOSUPPORT    => 501,
+			target::EPROTONOSUPPORT => 501,
+			target::ESOCKTNOSUPPORT => 501,
+			target::ENOPKG          => 501,
+			target::EXDEV           => 501,
+			target::ECONNREFUSED    => 502,
+			target::ECONNRESET      => 502,
+			target::ECONNABORTED    => 502,
+			target::EHOSTUNREACH    => 502,
+			target::ENETUNREACH     => 502,
+			target::EPROTO          => 502,
+			target::EREMOTEIO       => 502,
+			target::ENOLINK         => 502,
+			target::ECOMM           => 502,
+			target::EAGAIN          => 503,
+			target::EINTR           => 503,
+			target::EHOSTDOWN       => 503,
+			target::ENETDOWN        => 503,
+			target::ENETRESET       => 503,
+			target::ENONET          => 503,
+			target::ENOMEM          => 503,
+			target::ENOBUFS         => 503,
+			target::ENOSR           => 503,
+			target::EMFILE          => 503,
+			target::ENFILE          => 503,
+			target::EUSERS          => 503,
+			target::ENOLCK          => 503,
+			target::ETIMEDOUT       => 504,
+			target::ETIME           => 504,
+			target::ENOSPC          => 507,
+			target::EDQUOT          => 507,
+			target::EMLINK          => 507,
+			target::ELOOP           => 508,
+			_ => 500,
+		}
+	}
+
+	/// Returns the gRPC status code that best describes this error.
+	///
+	/// The mapping follows the conversion from error numbers to status codes
+	/// in Abseil (`absl::ErrnoToStatusCode`). Errors that are not listed
+	/// below are reported as `UNKNOWN`.
+	///
+	/// * `CANCELLED`: `ECANCELED`
+	/// * `INVALID_ARGUMENT`: `EINVAL`, `ENAMETOOLONG`, `E2BIG`, `EDESTADDRREQ`,
+	///   `EDOM`, `EFAULT`, `EILSEQ`, `ENOPROTOOPT`, `ENOSTR`, `ENOTSOCK`,
+	///   `ENOTTY`, `EPROTOTYPE`, `ESPIPE`
+	/// * `DEADLINE_EXCEEDED`: `ETIMEDOUT`, `ETIME`
+	/// * `NOT_FOUND`: `ENODEV`, `ENOENT`, `ENOMEDIUM`, `ENXIO`, `ESRCH`
+	/// * `ALREADY_EXISTS`: `EEXIST`, `EADDRNOTAVAIL`, `EALREADY`, `ENOTUNIQ`
+	/// * `PERMISSION_DENIED`: `EPERM`, `EACCES`, `ENOKEY`, `EROFS`
+	/// * `RESOURCE_EXHAUSTED`: `ENOSPC`, `EDQUOT`, `EMFILE`, `EMLINK`,
+	///   `ENFILE`, `ENOBUFS`, `ENODATA`, `ENOMEM`, `ENOSR`, `EUSERS`
+	/// * `FAILED_PRECONDITION`: `ENOTEMPTY`, `EISDIR`, `ENOTDIR`, `EADDRINUSE`,
+	///   `EBADF`, `EBADFD`, `EBUSY`, `ECHILD`, `EISCONN`, `EISNAM`, `ENOTBLK`,
+	///   `ENOTCONN`, `EPIPE`, `ESHUTDOWN`, `ETXTBSY`, `EUNATCH`
+	/// * `ABORTED`: `EDEADLK`, `ESTALE`
+	/// * `OUT_OF_RANGE`: `ECHRNG`, `EFBIG`, `EOVERFLOW`, `ERANGE`
+	/// * `UNIMPLEMENTED`: `ENOPKG`, `ENOSYS`, `EOPNOTSUPP`, `EAFNOSUPPORT`,
+	///   `EPFNOSUPPORT`, `EPROTONOSUPPORT`, `ESOCKTNOSUPPORT`, `EXDEV`
+	/// * `UNAVAILABLE`: `EAGAIN`, `ECOMM`, `ECONNREFUSED`, `ECONNABORTED`,
+	///   `ECONNRESET`, `EINTR`, `EHOSTDOWN`, `EHOSTUNREACH`, `ENETDOWN`,
+	///   `ENETRESET`, `ENETUNREACH`, `ENOLCK`, `ENOLINK`, `ENONET`
+	/// * `UNKNOWN`: `EADV`, `EBADE`, `EBADMSG`, `EBADR`, `EBADRQC`, `EBADSLT`,
+	///   `EBFONT`, `EDEADLOCK`, `EDOTDOT`, `EHWPOISON`, `EIDRM`, `EINIT`,
+	///   `EINPROGRESS`, `EIO`, `EKEYEXPIRED`, `EKEYREJECTED`, `EKEYREVOKED`,
+	///   `EL2HLT`, `EL2NSYNC`, `EL3HLT`, `EL3RST`, `ELIBACC`, `ELIBBAD`,
+	///   `ELIBEXEC`, `ELIBMAX`, `ELIBSCN`, `ELNRNG`, `ELOOP`, `EMEDIUMTYPE`,
+	///   `EMSGSIZE`, `EMULTIHOP`, `ENAVAIL`, `ENOANO`, `ENOCSI`, `ENOEXEC`,
+	///   `ENOMSG`, `ENOSYM`, `ENOTNAM`, `ENOTRECOVERABLE`, `EOWNERDEAD`,
+	///   `EPROCLIM`, `EPROTO`, `EREMCHG`, `EREMDEV`, `EREMOTE`, `EREMOTEIO`,
+	///   `EREMOTERELEASE`, `ERESTART`, `ERFKILL`, `ERREMOTE`, `ESRMNT`,
+	///   `ESTRPIPE`, `ETOOMANYREFS`, `EUCLEAN`, `EXFULL`
+	///
+	/// Aliases such as `EWOULDBLOCK` have the same status code as the error
+	/// they alias. On architectures where `EDEADLOCK` is distinct from
+	/// `EDEADLK`, it is reported as `UNKNOWN`.
+	pub const fn to_grpc_code(&self) -> i32 {
+		match *self {
+			target::ECANCELED       => GRPC_CANCELLED,
+			target::EINVAL          => GRPC_INVALID_ARGUMENT,
+			target::ENAMETOOLONG    => GRPC_INVALID_ARGUMENT,
+			target::E2BIG           => GRPC_INVALID_ARGUMENT,
+			target::EDESTADDRREQ    => GRPC_INVALID_ARGUMENT,
+			target::EDOM            => GRPC_INVALID_ARGUMENT,
+			target::EFAULT          => GRPC_INVALID_ARGUMENT,
+			target::EILSEQ          => GRPC_INVALID_ARGUMENT,
+			target::ENOPROTOOPT     => GRPC_INVALID_ARGUMENT,
+			target::ENOSTR          => GRPC_INVALID_ARGUMENT,
+			target::ENOTSOCK        => GRPC_INVALID_ARGUMENT,
+			target::ENOTTY          => GRPC_INVALID_ARGUMENT,
+			target::EPROTOTYPE      => GRPC_INVALID_ARGUMENT,
+			target::ESPIPE          => GRPC_INVALID_ARGUMENT,
+			target::ETIMEDOUT       => GRPC_DEADLINE_EXCEEDED,
+			target::ETIME           => GRPC_DEADLINE_EXCEEDED,
+			target::ENODEV          => GRPC_NOT_FOUND,
+			target::ENOENT          => GRPC_NOT_FOUND,
+			target::ENOMEDIUM       => GRPC_NOT_FOUND,
+			target::ENXIO           => GRPC_NOT_FOUND,
+			target::ESRCH           => GRPC_NOT_FOUND,
+			target::EEXIST          => GRPC_ALREADY_EXISTS,
+			target::EADDRNOTAVAIL   => GRPC_ALREADY_EXISTS,
+			target::EALREADY        => GRPC_ALREADY_EXISTS,
+			target::ENOTUNIQ        => GRPC_ALREADY_EXISTS,
+			target::EPERM           => GRPC_PERMISSION_DENIED,
+			target::EACCES          => GRPC_PERMISSION_DENIED,
+			target::ENOKEY          => GRPC_PERMISSION_DENIED,
+			target::EROFS           => GRPC_PERMISSION_DENIED,
+			target::ENOSPC          => GRPC_RESOURCE_EXHAUSTED,
+			target::EDQUOT          => GRPC_RESOURCE_EXHAUSTED,
+			target::EMFILE          => GRPC_RESOURCE_EXHAUSTED,
+			target::EMLINK          => GRPC_RESOURCE_EXHAUSTED,
+			target::ENFILE          => GRPC_RESOURCE_EXHAUSTED,
+			target::ENOBUFS         => GRPC_RESOURCE_EXHAUSTED,
+			target::ENODATA         => GRPC_RESOURCE_EXHAUSTED,
+			target::ENOMEM          => GRPC_RESOURCE_EXHAUSTED,
+			target::ENOSR           => GRPC_RESOURCE_EXHAUSTED,
+			target::EUSERS          => GRPC_RESOURCE_EXHAUSTED,
+			target::ENOTEMPTY       => GRPC_FAILED_PRECONDITION,
+			target::EISDIR          => GRPC_FAILED_PRECONDITION,
+			target::ENOTDIR         => GRPC_FAILED_PRECONDITION,
+			target::EADDRINUSE      => GRPC_FAILED_PRECONDITION,
+			target::EBADF           => GRPC_FAILED_PRECONDITION,
+			target::EBADFD          => GRPC_FAILED_PRECONDITION,
+			target::EBUSY           => GRPC_FAILED_PRECONDITION,
+			target::ECHILD          => GRPC_FAILED_PRECONDITION,
+			target::EISCONN         => GRPC_FAILED_PRECONDITION,
+			target::EISNAM          => GRPC_FAILED_PRECONDITION,
+			target::ENOTBLK         => GRPC_FAILED_PRECONDITION,
+			target::ENOTCONN        => GRPC_FAILED_PRECONDITION,
+			target::EPIPE           => GRPC_FAILED_PRECONDITION,
+			target::ESHUTDOWN       => GRPC_FAILED_PRECONDITION,
+			target::ETXTBSY         => GRPC_FAILED_PRECONDITION,
+			target::EUNATCH         => GRPC_FAILED_PRECONDITION,
+			target::EDEADLK         => GRPC_ABORTED,
+			target::ESTALE          => GRPC_ABORTED,
+			target::ECHRNG          => GRPC_OUT_OF_RANGE,
+			target::EFBIG           => GRPC_OUT_OF_RANGE,
+			target::EOVERFLOW       => GRPC_OUT_OF_RANGE,
+			target::ERANGE          => GRPC_OUT_OF_RANGE,
+			target::ENOPKG          => GRPC_UNIMPLEMENTED,
+			target::ENOSYS          => GRPC_UNIMPLEMENTED,
+			target::EOPNOTSUPP      => GRPC_UNIMPLEMENTED,
+			target::EAFNOSUPPORT    => GRPC_UNIMPLEMENTED,
+			target::EPFNOSUPPORT    => GRPC_UNIMPLEMENTED,
+			target::EPROTONOSUPPORT => GRPC_UNIMPLEMENTED,
+			target::ESOCKTNOSUPPORT => GRPC_UNIMPLEMENTED,
+			target::EXDEV           => GRPC_UNIMPLEMENTED,
+			target::EAGAIN          => GRPC_UNAVAILABLE,
+			target::ECOMM           => GRPC_UNAVAILABLE,
+			target::ECONNREFUSED    => GRPC_UNAVAILABLE,
+			target::ECONNABORTED    => GRPC_UNAVAILABLE,
+			target::ECONNRESET      => GRPC_UNAVAILABLE,
+			target::EINTR           => GRPC_UNAVAILABLE,
+			target::EHOSTDOWN       => GRPC_UNAVAILABLE,
+			target::EHOSTUNREACH    => GRPC_UNAVAILABLE,
+			target::ENETDOWN        => GRPC_UNAVAILABLE,
+			target::ENETRESET       => GRPC_UNAVAILABLE,
+			target::ENETUNREACH     => GRPC_UNAVAILABLE,
+			target::ENOLCK          => GRPC_UNAVAILABLE,
+			target::ENOLINK         => GRPC_UNAVAILABLE,
+			target::ENONET          => GRPC_UNAVAILABLE,
+			_ => GRPC_UNKNOWN,
+		}
+	}
+
+	/// Returns the `sysexits.h` exit code that best describes this error.
+	///
+	/// This is intended for command-line tools that exit when a system call
+	/// fails. Errors that are not listed below are reported as `EX_OSERR`.
+	///
+	/// * `EX_DATAERR`: `EINVAL`, `EDOM`, `ERANGE`, `EILSEQ`, `EBADMSG`,
+	///   `EOVERFLOW`
+	/// * `EX_NOINPUT`: `ENOENT`, `ENOTDIR`, `ENXIO`, `ENODEV`, `ENOMEDIUM`,
+	///   `ELOOP`, `ENAMETOOLONG`
+	/// * `EX_NOHOST`: `EHOSTUNREACH`, `EHOSTDOWN`
+	/// * `EX_UNAVAILABLE`: `ECONNREFUSED`, `ENETUNREACH`, `ENETDOWN`,
+	///   `ENONET`, `ENOSYS`, `EOPNOTSUPP`, `EAFNOSUPPORT`, `EPROTONOSUPPORT`,
+	///   `ENOPKG`
+	/// * `EX_SOFTWARE`: `EFAULT`, `EBADF`
+	/// * `EX_CANTCREAT`: `EEXIST`, `EISDIR`, `EROFS`, `ENOSPC`, `EDQUOT`,
+	///   `EFBIG`, `EMLINK`, `ETXTBSY`, `EXDEV`
+	/// * `EX_IOERR`: `EIO`, `EPIPE`, `EREMOTEIO`, `ESTALE`, `ECONNRESET`,
+	///   `ECONNABORTED`
+	/// * `EX_TEMPFAIL`: `EAGAIN`, `EBUSY`, `EINTR`, `ETIMEDOUT`, `ENOBUFS`,
+	///   `ENOLCK`, `EDEADLK`
+	/// * `EX_PROTOCOL`: `EPROTO`, `EPROTOTYPE`, `ENOPROTOOPT`
+	/// * `EX_NOPERM`: `EPERM`, `EACCES`, `EKEYREJECTED`
+	pub const fn to_sysexit(&self) -> u8 {
+		match *self {
+			target::EINVAL          => EX_DATAERR,
+			target::EDOM            => EX_DATAERR,
+			target::ERANGE          => EX_DATAERR,
+			target::EILSEQ          => EX_DATAERR,
+			target::EBADMSG         => EX_DATAERR,
+			target::EOVERFLOW       => EX_DATAERR,
+			target::ENOENT          => EX_NOINPUT,
+			target::ENOTDIR         => EX_NOINPUT,
+			target::ENXIO           => EX_NOINPUT,
+			target::ENODEV          => EX_NOINPUT,
+			target::ENOMEDIUM       => EX_NOINPUT,
+			target::ELOOP           => EX_NOINPUT,
+			target::ENAMETOOLONG    => EX_NOINPUT,
+			target::EHOSTUNREACH    => EX_NOHOST,
+			target::EHOSTDOWN       => EX_NOHOST,
+			target::ECONNREFUSED    => EX_UNAVAILABLE,
+			target::ENETUNREACH     => EX_UNAVAILABLE,
+			target::ENETDOWN        => EX_UNAVAILABLE,
+			target::ENONET          => EX_UNAVAILABLE,
+			target::ENOSYS          => EX_UNAVAILABLE,
+			target::EOPNOTSUPP      => EX_UNAVAILABLE,
+			target::EAFNOSUPPORT    => EX_UNAVAILABLE,
+			target::EPROTONOSUPPORT => EX_UNAVAILABLE,
+			target::ENOPKG          => EX_UNAVAILABLE,
+			target::EFAULT          => EX_SOFTWARE,
+			target::EBADF           => EX_SOFTWARE,
+			target::EEXIST          => EX_CANTCREAT,
+			target::EISDIR          => EX_CANTCREAT,
+			target::EROFS           => EX_CANTCREAT,
+			target::ENOSPC          => EX_CANTCREAT,
+			target::EDQUOT          => EX_CANTCREAT,
+			target::EFBIG           => EX_CANTCREAT,
+			target::EMLINK          => EX_CANTCREAT,
+			target::ETXTBSY         => EX_CANTCREAT,
+			target::EXDEV           => EX_CANTCREAT,
+			target::EIO             => EX_IOERR,
+			target::EPIPE           => EX_IOERR,
+			target::EREMOTEIO       => EX_IOERR,
+			target::ESTALE          => EX_IOERR,
+			target::ECONNRESET      => EX_IOERR,
+			target::ECONNABORTED    => EX_IOERR,
+			target::EAGAIN          => EX_TEMPFAIL,
+			target::EBUSY           => EX_TEMPFAIL,
+			target::EINTR           => EX_TEMPFAIL,
+			target::ETIMEDOUT       => EX_TEMPFAIL,
+			target::ENOBUFS         => EX_TEMPFAIL,
+			target::ENOLCK          => EX_TEMPFAIL,
+			target::EDEADLK         => EX_TEMPFAIL,
+			target::EPROTO          => EX_PROTOCOL,
+			target::EPROTOTYPE      => EX_PROTOCOL,
+			target::ENOPROTOOPT     => EX_PROTOCOL,
+			target::EPERM           => EX_NOPERM,
+			target::EACCES          => EX_NOPERM,
+			target::EKEYREJECTED    => EX_NOPERM,
+			_ => EX_OSERR,
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for process::ExitCode {
+	/// Converts an error to the exit code returned by
+	/// [`Error::to_sysexit`].
+	fn from(err: Error) -> process::ExitCode {
+		process::ExitCode::from(err.to_sysexit())
+	}
+}
+
+/// Result of a `main` function that exits with a `sysexits.h` exit code.
+///
+/// A `main` function that returns `Result<(), Error>` exits with status 1 on
+/// failure, because the standard library reports every `Err` the same way.
+/// Returning `Exit` instead reports the error to `stderr` in the same format,
+/// and then exits with the status returned by [`Error::to_sysexit`].
+///
+/// ```no_run
+/// fn run() -> Result<(), linux_errno::Error> {
+///     Err(linux_errno::ENOENT)
+/// }
+///
+/// fn main() -> linux_errno::Exit {
+///     run().into()
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Exit(pub Result<(), Error>);
+
+#[cfg(feature = "std")]
+impl From<Result<(), Error>> for Exit {
+	fn from(result: Result<(), Error>) -> Exit {
+		Exit(result)
+	}
+}
+
+#[cfg(feature = "std")]
+impl process::Termination for Exit {
+	fn report(self) -> process::ExitCode {
+		match self.0 {
+			Ok(()) => process::ExitCode::SUCCESS,
+			Err(err) => {
+				std::eprintln!("Error: {:?}", err);
+				err.into()
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::fmt;
+
+	use super::*;
+	use crate::arch::Arch;
+
+	// The tables in the documentation of each conversion.
+	const HTTP_STATUS: &[(u16, &[&str])] = &[
+		(400, &[
+			"EINVAL", "EDESTADDRREQ", "EDOM", "EILSEQ", "ENAMETOOLONG",
+			"ENOPROTOOPT", "ENOSTR", "ENOTSOCK", "ENOTTY", "EPROTOTYPE",
+			"ESPIPE", "ERANGE", "EOVERFLOW", "ECHRNG", "EBADMSG",
+		]),
+		(401, &[
+			"EKEYEXPIRED", "EKEYREVOKED", "EKEYREJECTED",
+		]),
+		(403, &[
+			"EPERM", "EACCES", "EROFS", "ENOKEY",
+		]),
+		(404, &[
+			"ENOENT", "ENODEV", "ENOMEDIUM", "ENXIO", "ESRCH",
+		]),
+		(409, &[
+			"EEXIST", "ENOTEMPTY", "ENOTDIR", "EISDIR", "EBUSY", "ETXTBSY",
+			"EALREADY", "EISCONN", "EDEADLK", "EADDRINUSE", "EADDRNOTAVAIL",
+			"ENOTUNIQ",
+		]),
+		(410, &[
+			"ESTALE", "EIDRM",
+		]),
+		(413, &[
+			"E2BIG", "EFBIG", "EMSGSIZE",
+		]),
+		(499, &[
+			"ECANCELED",
+		]),
+		(501, &[
+			"ENOSYS", "EOPNOTSUPP", "EAFNOSUPPORT", "EPFNOSUPPORT",
+			"EPROTONOSUPPORT", "ESOCKTNOSUPPORT", "ENOPKG", "EXDEV",
+		]),
+		(502, &[
+			"ECONNREFUSED", "ECONNRESET", "ECONNABORTED", "EHOSTUNREACH",
+			"ENETUNREACH", "EPROTO", "EREMOTEIO", "ENOLINK", "ECOMM",
+		]),
+		(503, &[
+			"EAGAIN", "EINTR", "EHOSTDOWN", "ENETDOWN", "ENETRESET", "ENONET",
+			"ENOMEM", "ENOBUFS", "ENOSR", "EMFILE", "ENFILE", "EUSERS",
+			"ENOLCK",
+		]),
+		(504, &[
+			"ETIMEDOUT", "ETIME",
+		]),
+		(507, &[
+			"ENOSPC", "EDQUOT", "EMLINK",
+		]),
+		(508, &[
+			"ELOOP",
+		]),
+		(500, &[
+			"EADV", "EBADE", "EBADF", "EBADFD", "EBADR", "EBADRQC", "EBADSLT",
+			"EBFONT", "ECHILD", "EDEADLOCK", "EDOTDOT", "EFAULT", "EHWPOISON",
+			"EINIT", "EINPROGRESS", "EIO", "EISNAM", "EL2HLT", "EL2NSYNC",
+			"EL3HLT", "EL3RST", "ELIBACC", "ELIBBAD", "ELIBEXEC", "ELIBMAX",
+			"ELIBSCN", "ELNRNG", "EMEDIUMTYPE", "EMULTIHOP", "ENAVAIL",
+			"ENOANO", "ENOCSI", "ENODATA", "ENOEXEC", "ENOMSG", "ENOSYM",
+			"ENOTBLK", "ENOTCONN", "ENOTNAM", "ENOTRECOVERABLE", "EOWNERDEAD",
+			"EPIPE", "EPROCLIM", "EREMCHG", "EREMDEV", "EREMOTE",
+			"EREMOTERELEASE", "ERESTART", "ERFKILL", "ERREMOTE", "ESHUTDOWN",
+			"ESRMNT", "ESTRPIPE", "ETOOMANYREFS", "EUCLEAN", "EUNATCH",
+			"EXFULL",
+		]),
+	];
+
+	const GRPC_CODE: &[(i32, &[&str])] = &[
+		(GRPC_CANCELLED, &[
+			"ECANCELED",
+		]),
+		(GRPC_INVALID_ARGUMENT, &[
+			"EINVAL", "ENAMETOOLONG", "E2BIG", "EDESTADDRREQ", "EDOM", "EFAULT",
+			"EILSEQ", "ENOPROTOOPT", "ENOSTR", "ENOTSOCK", "ENOTTY",
+			"EPROTOTYPE", "ESPIPE",
+		]),
+		(GRPC_DEADLINE_EXCEEDED, &[
+			"ETIMEDOUT", "ETIME",
+		]),
+		(GRPC_NOT_FOUND, &[
+			"ENODEV", "ENOENT", "ENOMEDIUM", "ENXIO", "ESRCH",
+		]),
+		(GRPC_ALREADY_EXISTS, &[
+			"EEXIST", "EADDRNOTAVAIL", "EALREADY", "ENOTUNIQ",
+		]),
+		(GRPC_PERMISSION_DENIED, &[
+			"EPERM", "EACCES", "ENOKEY", "EROFS",
+		]),
+		(GRPC_RESOURCE_EXHAUSTED, &[
+			"ENOSPC", "EDQUOT", "EMFILE", "EMLINK", "ENFILE", "ENOBUFS",
+			"ENODATA", "ENOMEM", "ENOSR", "EUSERS",
+		]),
+		(GRPC_FAILED_PRECONDITION, &[
+			"ENOTEMPTY", "EISDIR", "ENOTDIR", "EADDRINUSE", "EBADF", "EBADFD",
+			"EBUSY", "ECHILD", "EISCONN", "EISNAM", "ENOTBLK", "ENOTCONN",
+			"EPIPE", "ESHUTDOWN", "ETXTBSY", "EUNATCH",
+		]),
+		(GRPC_ABORTED, &[
+			"EDEADLK", "ESTALE",
+		]),
+		(GRPC_OUT_OF_RANGE, &[
+			"ECHRNG", "EFBIG", "EOVERFLOW", "ERANGE",
+		]),
+		(GRPC_UNIMPLEMENTED, &[
+			"ENOPKG", "ENOSYS", "EOPNOTSUPP", "EAFNOSUPPORT", "EPFNOSUPPORT",
+			"EPROTONOSUPPORT", "ESOCKTNOSUPPORT", "EXDEV",
+		]),
+		(GRPC_UNAVAILABLE, &[
+			"EAGAIN", "ECOMM", "ECONNREFUSED", "ECONNABORTED", "ECONNRESET",
+			"EINTR", "EHOSTDOWN", "EHOSTUNREACH", "ENETDOWN", "ENETRESET",
+			"ENETUNREACH", "ENOLCK", "ENOLINK", "ENONET",
+		]),
+		(GRPC_UNKNOWN, &[
+			"EADV", "EBADE", "EBADMSG", "EBADR", "EBADRQC", "EBADSLT", "EBFONT",
+			"EDEADLOCK", "EDOTDOT", "EHWPOISON", "EIDRM", "EINIT",
+			"EINPROGRESS", "EIO", "EKEYEXPIRED", "EKEYREJECTED", "EKEYREVOKED",
+			"EL2HLT", "EL2NSYNC", "EL3HLT", "EL3RST", "ELIBACC", "ELIBBAD",
+			"ELIBEXEC", "ELIBMAX", "ELIBSCN", "ELNRNG", "ELOOP", "EMEDIUMTYPE",
+			"EMSGSIZE", "EMULTIHOP", "ENAVAIL", "ENOANO", "ENOCSI", "ENOEXEC",
+			"ENOMSG", "ENOSYM", "ENOTNAM", "ENOTRECOVERABLE", "EOWNERDEAD",
+			"EPROCLIM", "EPROTO", "EREMCHG", "EREMDEV", "EREMOTE", "EREMOTEIO",
+			"EREMOTERELEASE", "ERESTART", "ERFKILL", "ERREMOTE", "ESRMNT",
+			"ESTRPIPE", "ETOOMANYREFS", "EUCLEAN", "EXFULL",
+		]),
+	];
+
+	fn documented<T: Copy>(rows: &[(T, &[&str])], name: &str) -> Option<T> {
+		rows.iter()
+			.find(|(_, names)| names.contains(&name))
+			.map(|&(code, _)| code)
+	}
+
+	const SYSEXIT: &[(u8, &[&str])] = &[
+		(EX_DATAERR, &[
+			"EINVAL", "EDOM", "ERANGE", "EILSEQ", "EBADMSG", "EOVERFLOW",
+		]),
+		(EX_NOINPUT, &[
+			"ENOENT", "ENOTDIR", "ENXIO", "ENODEV", "ENOMEDIUM", "ELOOP",
+			"ENAMETOOLONG",
+		]),
+		(EX_NOHOST, &[
+			"EHOSTUNREACH", "EHOSTDOWN",
+		]),
+		(EX_UNAVAILABLE, &[
+			"ECONNREFUSED", "ENETUNREACH", "ENETDOWN", "ENONET", "ENOSYS",
+			"EOPNOTSUPP", "EAFNOSUPPORT", "EPROTONOSUPPORT", "ENOPKG",
+		]),
+		(EX_SOFTWARE, &[
+			"EFAULT", "EBADF",
+		]),
+		(EX_CANTCREAT, &[
+			"EEXIST", "EISDIR", "EROFS", "ENOSPC", "EDQUOT", "EFBIG", "EMLINK",
+			"ETXTBSY", "EXDEV",
+		]),
+		(EX_IOERR, &[
+			"EIO", "EPIPE", "EREMOTEIO", "ESTALE", "ECONNRESET", "ECONNABORTED",
+		]),
+		(EX_TEMPFAIL, &[
+			"EAGAIN", "EBUSY", "EINTR", "ETIMEDOUT", "ENOBUFS", "ENOLCK",
+			"EDEADLK",
+		]),
+		(EX_PROTOCOL, &[
+			"EPROTO", "EPROTOTYPE", "ENOPROTOOPT",
+		]),
+		(EX_NOPERM, &[
+			"EPERM", "EACCES", "EKEYREJECTED",
+		]),
+	];
+
+	// Checks that every error number of the current target has the code
+	// that its name is documented with, or `default` if it is not listed.
+	fn check_documented<T>(
+		rows: &[(T, &[&str])],
+		default: Option<T>,
+		convert: fn(&Error) -> T,
+	) where
+		T: Copy + PartialEq + fmt::Debug,
+	{
+		// Aliases have the same code as the error they alias, so only the
+		// primary names are checked.
+		for entry in target::ERRNOS {
+			let code = documented(rows, entry.name)
+				.or_else(|| {
+					// Such as `ECANCELLED` on parisc, which is documented
+					// by its alias `ECANCELED`.
+					target::ALIASES
+						.iter()
+						.filter(|alias| alias.error == entry.error)
+						.find_map(|alias| documented(rows, alias.name))
+				})
+				.or(default);
+			assert_eq!(Some(convert(&entry.error)), code, "{}", entry.name);
+		}
+
+		// Every documented name is defined on some architecture.
+		for &(_, names) in rows {
+			for name in names {
+				let defined = Arch::ALL
+					.iter()
+					.any(|arch| arch.err_from_name(name).is_some());
+				assert!(defined, "{}", name);
+			}
+		}
+	}
+
+	#[test]
+	fn http_status() {
+		check_documented(HTTP_STATUS, None, Error::to_http_status);
+
+		assert_eq!(target::ENOENT.to_http_status(), 404);
+		assert_eq!(target::EACCES.to_http_status(), 403);
+		assert_eq!(target::ENOSPC.to_http_status(), 507);
+		assert_eq!(target::EWOULDBLOCK.to_http_status(), 503);
+		assert_eq!(Error::new(4000).unwrap().to_http_status(), 500);
+	}
+
+	#[test]
+	fn grpc_code() {
+		check_documented(GRPC_CODE, None, Error::to_grpc_code);
+
+		assert_eq!(target::ETIMEDOUT.to_grpc_code(), GRPC_DEADLINE_EXCEEDED);
+		assert_eq!(target::ENOENT.to_grpc_code(), GRPC_NOT_FOUND);
+		assert_eq!(target::EWOULDBLOCK.to_grpc_code(), GRPC_UNAVAILABLE);
+		assert_eq!(Error::new(4000).unwrap().to_grpc_code(), GRPC_UNKNOWN);
+	}
+
+	#[test]
+	fn sysexit() {
+		check_documented(SYSEXIT, Some(EX_OSERR), Error::to_sysexit);
+
+		assert_eq!(target::ENOENT.to_sysexit(), EX_NOINPUT);
+		assert_eq!(target::EACCES.to_sysexit(), EX_NOPERM);
+		assert_eq!(target::ENOMEM.to_sysexit(), EX_OSERR);
+		assert_eq!(target::EWOULDBLOCK.to_sysexit(), EX_TEMPFAIL);
+		assert_eq!(Error::new(4000).unwrap().to_sysexit(), EX_OSERR);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn exit_code() {
+		use std::process::{ExitCode, Termination};
+
+		assert_eq!(
+			ExitCode::from(target::ENOENT),
+			ExitCode::from(EX_NOINPUT),
+		);
+		assert_eq!(Exit::from(Ok(())).report(), ExitCode::SUCCESS);
+		assert_eq!(
+			Exit::from(Err(target::EACCES)).report(),
+			ExitCode::from(EX_NOPERM),
+		);
+	}
+}
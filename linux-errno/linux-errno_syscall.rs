@@ -0,0 +1,508 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Raw Linux system calls.
+//!
+//! The `syscall0` through `syscall6` functions invoke a system call with
+//! zero to six arguments, and decode the return value into a `Result`. They
+//! do not depend on the C library, so they can be used in static binaries
+//! that don't link against libc.
+//!
+//! System call numbers are architecture-specific, and are not defined by
+//! this module.
+//!
+//! This module is available on `x86_64`, `aarch64`, and `riscv64` targets
+//! when the `syscall` feature is enabled.
+//!
+//! ```no_run
+//! # use linux_errno::syscall::syscall3;
+//! const SYS_WRITE: usize = 1; // x86_64
+//!
+//! let msg = b"hello\n";
+//! let result = unsafe {
+//!     syscall3(SYS_WRITE, 1, msg.as_ptr() as usize, msg.len())
+//! };
+//! ```
+
+use core::arch::asm;
+
+use crate::{ErrOrUsize, Error};
+
+#[inline]
+fn decode(ret: usize) -> Result<usize, Error> {
+	ErrOrUsize::from_raw(ret).into_result()
+}
+
+/// Invokes the system call `nr` with zero arguments.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall0(nr: usize) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		lateout("x0") ret,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		lateout("a0") ret,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+/// Invokes the system call `nr` with one argument.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall1(nr: usize, a1: usize) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		in("rdi") a1,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		inlateout("x0") a1 => ret,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		inlateout("a0") a1 => ret,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+/// Invokes the system call `nr` with two arguments.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall2(
+	nr: usize,
+	a1: usize,
+	a2: usize,
+) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		in("rdi") a1,
+		in("rsi") a2,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		inlateout("x0") a1 => ret,
+		in("x1") a2,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		inlateout("a0") a1 => ret,
+		in("a1") a2,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+/// Invokes the system call `nr` with three arguments.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall3(
+	nr: usize,
+	a1: usize,
+	a2: usize,
+	a3: usize,
+) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		in("rdi") a1,
+		in("rsi") a2,
+		in("rdx") a3,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		inlateout("x0") a1 => ret,
+		in("x1") a2,
+		in("x2") a3,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		inlateout("a0") a1 => ret,
+		in("a1") a2,
+		in("a2") a3,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+/// Invokes the system call `nr` with four arguments.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall4(
+	nr: usize,
+	a1: usize,
+	a2: usize,
+	a3: usize,
+	a4: usize,
+) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		in("rdi") a1,
+		in("rsi") a2,
+		in("rdx") a3,
+		in("r10") a4,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		inlateout("x0") a1 => ret,
+		in("x1") a2,
+		in("x2") a3,
+		in("x3") a4,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		inlateout("a0") a1 => ret,
+		in("a1") a2,
+		in("a2") a3,
+		in("a3") a4,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+/// Invokes the system call `nr` with five arguments.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall5(
+	nr: usize,
+	a1: usize,
+	a2: usize,
+	a3: usize,
+	a4: usize,
+	a5: usize,
+) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		in("rdi") a1,
+		in("rsi") a2,
+		in("rdx") a3,
+		in("r10") a4,
+		in("r8") a5,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		inlateout("x0") a1 => ret,
+		in("x1") a2,
+		in("x2") a3,
+		in("x3") a4,
+		in("x4") a5,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		inlateout("a0") a1 => ret,
+		in("a1") a2,
+		in("a2") a3,
+		in("a3") a4,
+		in("a4") a5,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+/// Invokes the system call `nr` with six arguments.
+///
+/// # Safety
+///
+/// The caller must ensure that the system call and its arguments are valid,
+/// as for any direct system call.
+#[inline]
+pub unsafe fn syscall6(
+	nr: usize,
+	a1: usize,
+	a2: usize,
+	a3: usize,
+	a4: usize,
+	a5: usize,
+	a6: usize,
+) -> Result<usize, Error> {
+	let ret: usize;
+	#[cfg(target_arch = "x86_64")]
+	asm!(
+		"syscall",
+		inlateout("rax") nr => ret,
+		in("rdi") a1,
+		in("rsi") a2,
+		in("rdx") a3,
+		in("r10") a4,
+		in("r8") a5,
+		in("r9") a6,
+		out("rcx") _,
+		out("r11") _,
+		options(nostack),
+	);
+	#[cfg(target_arch = "aarch64")]
+	asm!(
+		"svc 0",
+		in("x8") nr,
+		inlateout("x0") a1 => ret,
+		in("x1") a2,
+		in("x2") a3,
+		in("x3") a4,
+		in("x4") a5,
+		in("x5") a6,
+		options(nostack),
+	);
+	#[cfg(target_arch = "riscv64")]
+	asm!(
+		"ecall",
+		in("a7") nr,
+		inlateout("a0") a1 => ret,
+		in("a1") a2,
+		in("a2") a3,
+		in("a3") a4,
+		in("a4") a5,
+		in("a5") a6,
+		options(nostack),
+	);
+	decode(ret)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{process, thread};
+
+	use super::*;
+	use crate::target;
+
+	#[cfg(target_arch = "x86_64")]
+	mod nr {
+		pub(super) const READ: usize = 0;
+		pub(super) const WRITE: usize = 1;
+		pub(super) const CLOSE: usize = 3;
+		pub(super) const MMAP: usize = 9;
+		pub(super) const MUNMAP: usize = 11;
+		pub(super) const PREAD64: usize = 17;
+		pub(super) const GETPID: usize = 39;
+		pub(super) const PRCTL: usize = 157;
+		pub(super) const PIPE2: usize = 293;
+	}
+
+	#[cfg(any(target_arch = "aarch64", target_arch = "riscv64"))]
+	mod nr {
+		pub(super) const READ: usize = 63;
+		pub(super) const WRITE: usize = 64;
+		pub(super) const CLOSE: usize = 57;
+		pub(super) const MMAP: usize = 222;
+		pub(super) const MUNMAP: usize = 215;
+		pub(super) const PREAD64: usize = 67;
+		pub(super) const GETPID: usize = 172;
+		pub(super) const PRCTL: usize = 167;
+		pub(super) const PIPE2: usize = 59;
+	}
+
+	const BAD_FD: usize = -1i32 as usize;
+
+	#[test]
+	fn getpid() {
+		let pid = unsafe { syscall0(nr::GETPID) };
+		assert_eq!(pid, Ok(process::id() as usize));
+	}
+
+	#[test]
+	fn bad_fd() {
+		let mut buf = [0u8; 8];
+		let buf_ptr = buf.as_mut_ptr() as usize;
+		unsafe {
+			assert_eq!(syscall1(nr::CLOSE, BAD_FD), Err(target::EBADF));
+			assert_eq!(
+				syscall3(nr::READ, BAD_FD, buf_ptr, buf.len()),
+				Err(target::EBADF),
+			);
+		}
+	}
+
+	#[test]
+	fn pipe() {
+		let mut fds = [0i32; 2];
+		let fds_ptr = fds.as_mut_ptr() as usize;
+		assert_eq!(unsafe { syscall2(nr::PIPE2, fds_ptr, 0) }, Ok(0));
+		let (r, w) = (fds[0] as usize, fds[1] as usize);
+
+		let msg = b"hello";
+		let mut buf = [0u8; 8];
+		let buf_ptr = buf.as_mut_ptr() as usize;
+		unsafe {
+			let written =
+				syscall3(nr::WRITE, w, msg.as_ptr() as usize, msg.len());
+			assert_eq!(written, Ok(msg.len()));
+			let read = syscall3(nr::READ, r, buf_ptr, buf.len());
+			assert_eq!(read, Ok(msg.len()));
+			assert_eq!(
+				syscall4(nr::PREAD64, r, buf_ptr, buf.len(), 0),
+				Err(target::ESPIPE),
+			);
+			assert_eq!(syscall1(nr::CLOSE, r), Ok(0));
+			assert_eq!(syscall1(nr::CLOSE, w), Ok(0));
+		}
+		assert_eq!(&buf[..msg.len()], msg);
+	}
+
+	#[test]
+	fn prctl() {
+		const PR_GET_NAME: usize = 16;
+
+		let name = thread::Builder::new()
+			.name("syscall-test".into())
+			.spawn(|| {
+				let mut buf = [0u8; 16];
+				let buf_ptr = buf.as_mut_ptr() as usize;
+				let ret = unsafe {
+					syscall5(nr::PRCTL, PR_GET_NAME, buf_ptr, 0, 0, 0)
+				};
+				assert_eq!(ret, Ok(0));
+				buf
+			})
+			.unwrap()
+			.join()
+			.unwrap();
+		assert_eq!(&name[..13], b"syscall-test\0");
+
+		let ret = unsafe { syscall5(nr::PRCTL, usize::MAX, 0, 0, 0, 0) };
+		assert_eq!(ret, Err(target::EINVAL));
+	}
+
+	#[test]
+	fn mmap() {
+		const PROT_READ_WRITE: usize = 0x3;
+		const MAP_PRIVATE_ANONYMOUS: usize = 0x22;
+		const LEN: usize = 4096;
+
+		let addr = unsafe {
+			syscall6(
+				nr::MMAP,
+				0,
+				LEN,
+				PROT_READ_WRITE,
+				MAP_PRIVATE_ANONYMOUS,
+				BAD_FD,
+				0,
+			)
+		}
+		.unwrap();
+		unsafe {
+			*(addr as *mut u8) = 1;
+			assert_eq!(syscall2(nr::MUNMAP, addr, LEN), Ok(0));
+		}
+
+		// An unaligned offset is rejected.
+		let ret = unsafe {
+			syscall6(
+				nr::MMAP,
+				0,
+				LEN,
+				PROT_READ_WRITE,
+				MAP_PRIVATE_ANONYMOUS,
+				BAD_FD,
+				1,
+			)
+		};
+		assert_eq!(ret, Err(target::EINVAL));
+	}
+}
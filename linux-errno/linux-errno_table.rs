@@ -0,0 +1,285 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+// Lookup tables for error names and messages.
+//
+// Each table maps error numbers to entries with a dense array of indexes,
+// covering the error numbers from the smallest defined error number up to
+// `MAX_DENSE_SPAN` above it. Error numbers beyond the dense range (such as
+// `EDQUOT` on mips, which is 1133) are stored in a short sparse list.
+//
+// The tables are computed at compile time by the `errno_table!` macro.
+
+use crate::{Entry, Error};
+
+const MAX_DENSE_SPAN: u16 = 512;
+
+// Sparse entries are searched linearly, so there must be few of them.
+const MAX_SPARSE_LEN: usize = 4;
+
+#[derive(Clone, Copy)]
+pub(crate) struct Table {
+	base: u16,
+	dense: &'static [u8],
+	sparse: &'static [Entry],
+	entries: &'static [Entry],
+}
+
+impl Table {
+	pub(crate) const fn new(
+		entries: &'static [Entry],
+		dense: &'static [u8],
+		sparse: &'static [Entry],
+	) -> Table {
+		Table {
+			base: base(entries),
+			dense,
+			sparse,
+			entries,
+		}
+	}
+
+	#[inline]
+	pub(crate) const fn get(&self, err: Error) -> Option<&'static Entry> {
+		let errno = err.0.get();
+		let offset = errno.wrapping_sub(self.base) as usize;
+		if offset < self.dense.len() {
+			return match self.dense[offset] {
+				0 => None,
+				idx => Some(&self.entries[idx as usize - 1]),
+			};
+		}
+		let mut i = 0;
+		while i < self.sparse.len() {
+			if self.sparse[i].error.0.get() == errno {
+				return Some(&self.sparse[i]);
+			}
+			i += 1;
+		}
+		None
+	}
+
+	#[inline]
+	pub(crate) const fn err_name(&self, err: Error) -> Option<&'static str> {
+		match self.get(err) {
+			Some(entry) => Some(entry.name),
+			None => None,
+		}
+	}
+
+	#[inline]
+	pub(crate) const fn err_message(
+		&self,
+		err: Error,
+	) -> Option<&'static str> {
+		match self.get(err) {
+			Some(entry) => Some(entry.message),
+			None => None,
+		}
+	}
+}
+
+const fn base(entries: &[Entry]) -> u16 {
+	let mut base = u16::MAX;
+	let mut i = 0;
+	while i < entries.len() {
+		let errno = entries[i].error.0.get();
+		if errno < base {
+			base = errno;
+		}
+		i += 1;
+	}
+	base
+}
+
+const fn is_dense(base: u16, errno: u16) -> bool {
+	errno - base < MAX_DENSE_SPAN
+}
+
+pub(crate) const fn dense_len(entries: &[Entry]) -> usize {
+	let base = base(entries);
+	let mut len = 0;
+	let mut i = 0;
+	while i < entries.len() {
+		let errno = entries[i].error.0.get();
+		if is_dense(base, errno) && (errno - base) as usize >= len {
+			len = (errno - base) as usize + 1;
+		}
+		i += 1;
+	}
+	len
+}
+
+pub(crate) const fn dense<const N: usize>(entries: &[Entry]) -> [u8; N] {
+	if entries.len() >= u8::MAX as usize {
+		panic!("too many entries for a dense errno table");
+	}
+	let base = base(entries);
+	let mut dense = [0u8; N];
+	let mut i = 0;
+	while i < entries.len() {
+		let errno = entries[i].error.0.get();
+		if is_dense(base, errno) {
+			let offset = (errno - base) as usize;
+			if dense[offset] == 0 {
+				dense[offset] = (i + 1) as u8;
+			}
+		}
+		i += 1;
+	}
+	dense
+}
+
+pub(crate) const fn sparse_len(entries: &[Entry]) -> usize {
+	let base = base(entries);
+	let mut len = 0;
+	let mut i = 0;
+	while i < entries.len() {
+		if !is_dense(base, entries[i].error.0.get()) {
+			len += 1;
+		}
+		i += 1;
+	}
+	if len > MAX_SPARSE_LEN {
+		panic!("too many sparse entries in errno table");
+	}
+	len
+}
+
+pub(crate) const fn sparse<const N: usize>(entries: &[Entry]) -> [Entry; N] {
+	let base = base(entries);
+	let mut sparse = [entries[0]; N];
+	let mut len = 0;
+	let mut i = 0;
+	while i < entries.len() {
+		if !is_dense(base, entries[i].error.0.get()) {
+			sparse[len] = entries[i];
+			len += 1;
+		}
+		i += 1;
+	}
+	sparse
+}
+
+// Expands the architecture files into `match`-based lookup functions, which
+// is how names and messages were looked up before the tables were added.
+// The tests check that both lookups agree.
+#[cfg(test)]
+#[allow(clippy::duplicate_mod)]
+#[path = "."]
+mod match_lookup {
+	macro_rules! errno_constants {
+		( $( #[doc = $doc:literal] $name:ident = $value:literal , )+ ) => {
+			pub(crate) fn err_name(errno: u16) -> Option<&'static str> {
+				match errno {
+				$(
+					$value => Some(stringify!($name)),
+				)*
+					_ => None,
+				}
+			}
+
+			pub(crate) fn err_message(errno: u16) -> Option<&'static str> {
+				match errno {
+				$(
+					$value => Some($doc.trim_start()),
+				)*
+					_ => None,
+				}
+			}
+		}
+	}
+
+	macro_rules! errno_aliases {
+		( $( $tt:tt )* ) => {};
+	}
+
+	#[macro_use]
+	#[path = "linux-errno_generic.rs"]
+	pub(crate) mod generic;
+
+	#[path = "linux-errno_alpha.rs"]
+	pub(crate) mod alpha;
+
+	#[path = "linux-errno_mips.rs"]
+	pub(crate) mod mips;
+
+	#[path = "linux-errno_parisc.rs"]
+	pub(crate) mod parisc;
+
+	#[path = "linux-errno_powerpc.rs"]
+	pub(crate) mod powerpc;
+
+	#[path = "linux-errno_sparc.rs"]
+	pub(crate) mod sparc;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::arch::Arch;
+
+	type Lookup = fn(u16) -> Option<&'static str>;
+
+	fn match_lookups(arch: Arch) -> (Lookup, Lookup) {
+		use super::match_lookup::*;
+
+		match arch {
+			Arch::Alpha => (alpha::err_name, alpha::err_message),
+			Arch::Mips => (mips::err_name, mips::err_message),
+			Arch::Parisc => (parisc::err_name, parisc::err_message),
+			Arch::Powerpc => (powerpc::err_name, powerpc::err_message),
+			Arch::Sparc => (sparc::err_name, sparc::err_message),
+			_ => (generic::err_name, generic::err_message),
+		}
+	}
+
+	#[test]
+	fn matches_match_lookup() {
+		for &arch in Arch::ALL.iter() {
+			let (err_name, err_message) = match_lookups(arch);
+			for errno in 1..0x1000 {
+				let err = Error::new(errno).unwrap();
+				assert_eq!(arch.err_name(err), err_name(errno), "{:?}", err);
+				assert_eq!(
+					arch.err_message(err),
+					err_message(errno),
+					"{:?}",
+					err,
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn outliers() {
+		let edquot = Error::new(1133).unwrap();
+		assert_eq!(Arch::Mips.err_name(edquot), Some("EDQUOT"));
+		assert_eq!(Arch::Mips.err_message(edquot), Some("Quota exceeded"));
+		assert_eq!(Arch::Generic.err_name(edquot), None);
+
+		let edeadlock = Error::new(58).unwrap();
+		assert_eq!(Arch::Powerpc.err_name(edeadlock), Some("EDEADLOCK"));
+		assert_eq!(
+			Arch::Powerpc.err_message(edeadlock),
+			Some("File locking deadlock error"),
+		);
+		assert_eq!(Arch::X86.err_name(edeadlock), None);
+
+		let edeadlk = Error::new(35).unwrap();
+		assert_eq!(Arch::Powerpc.err_name(edeadlk), Some("EDEADLK"));
+		assert_eq!(Arch::X86.err_name(edeadlk), Some("EDEADLK"));
+	}
+}
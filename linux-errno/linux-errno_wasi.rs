@@ -0,0 +1,396 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Conversions between Linux error numbers and WASI error codes.
+//!
+//! WASI (`wasi_snapshot_preview1`) defines its own `errno` enumeration, which
+//! is a subset of the POSIX error numbers plus the WASI-specific
+//! [`ERRNO_NOTCAPABLE`]. Every WASI error code has an exact Linux equivalent
+//! except `ERRNO_NOTCAPABLE`, which is converted to `EPERM`.
+//!
+//! Linux error numbers without a WASI equivalent can be converted with
+//! [`to_wasi_lossy`], which substitutes the closest WASI error code.
+//!
+//! Linux error numbers are interpreted according to the current target
+//! architecture.
+
+use crate::Error;
+use crate::target;
+
+// https://github.com/WebAssembly/WASI/blob/main/legacy/preview1/witx/typenames.witx
+
+pub const ERRNO_2BIG: u16 = 1;
+pub const ERRNO_ACCES: u16 = 2;
+pub const ERRNO_ADDRINUSE: u16 = 3;
+pub const ERRNO_ADDRNOTAVAIL: u16 = 4;
+pub const ERRNO_AFNOSUPPORT: u16 = 5;
+pub const ERRNO_AGAIN: u16 = 6;
+pub const ERRNO_ALREADY: u16 = 7;
+pub const ERRNO_BADF: u16 = 8;
+pub const ERRNO_BADMSG: u16 = 9;
+pub const ERRNO_BUSY: u16 = 10;
+pub const ERRNO_CANCELED: u16 = 11;
+pub const ERRNO_CHILD: u16 = 12;
+pub const ERRNO_CONNABORTED: u16 = 13;
+pub const ERRNO_CONNREFUSED: u16 = 14;
+pub const ERRNO_CONNRESET: u16 = 15;
+pub const ERRNO_DEADLK: u16 = 16;
+pub const ERRNO_DESTADDRREQ: u16 = 17;
+pub const ERRNO_DOM: u16 = 18;
+pub const ERRNO_DQUOT: u16 = 19;
+pub const ERRNO_EXIST: u16 = 20;
+pub const ERRNO_FAULT: u16 = 21;
+pub const ERRNO_FBIG: u16 = 22;
+pub const ERRNO_HOSTUNREACH: u16 = 23;
+pub const ERRNO_IDRM: u16 = 24;
+pub const ERRNO_ILSEQ: u16 = 25;
+pub const ERRNO_INPROGRESS: u16 = 26;
+pub const ERRNO_INTR: u16 = 27;
+pub const ERRNO_INVAL: u16 = 28;
+pub const ERRNO_IO: u16 = 29;
+pub const ERRNO_ISCONN: u16 = 30;
+pub const ERRNO_ISDIR: u16 = 31;
+pub const ERRNO_LOOP: u16 = 32;
+pub const ERRNO_MFILE: u16 = 33;
+pub const ERRNO_MLINK: u16 = 34;
+pub const ERRNO_MSGSIZE: u16 = 35;
+pub const ERRNO_MULTIHOP: u16 = 36;
+pub const ERRNO_NAMETOOLONG: u16 = 37;
+pub const ERRNO_NETDOWN: u16 = 38;
+pub const ERRNO_NETRESET: u16 = 39;
+pub const ERRNO_NETUNREACH: u16 = 40;
+pub const ERRNO_NFILE: u16 = 41;
+pub const ERRNO_NOBUFS: u16 = 42;
+pub const ERRNO_NODEV: u16 = 43;
+pub const ERRNO_NOENT: u16 = 44;
+pub const ERRNO_NOEXEC: u16 = 45;
+pub const ERRNO_NOLCK: u16 = 46;
+pub const ERRNO_NOLINK: u16 = 47;
+pub const ERRNO_NOMEM: u16 = 48;
+pub const ERRNO_NOMSG: u16 = 49;
+pub const ERRNO_NOPROTOOPT: u16 = 50;
+pub const ERRNO_NOSPC: u16 = 51;
+pub const ERRNO_NOSYS: u16 = 52;
+pub const ERRNO_NOTCONN: u16 = 53;
+pub const ERRNO_NOTDIR: u16 = 54;
+pub const ERRNO_NOTEMPTY: u16 = 55;
+pub const ERRNO_NOTRECOVERABLE: u16 = 56;
+pub const ERRNO_NOTSOCK: u16 = 57;
+pub const ERRNO_NOTSUP: u16 = 58;
+pub const ERRNO_NOTTY: u16 = 59;
+pub const ERRNO_NXIO: u16 = 60;
+pub const ERRNO_OVERFLOW: u16 = 61;
+pub const ERRNO_OWNERDEAD: u16 = 62;
+pub const ERRNO_PERM: u16 = 63;
+pub const ERRNO_PIPE: u16 = 64;
+pub const ERRNO_PROTO: u16 = 65;
+pub const ERRNO_PROTONOSUPPORT: u16 = 66;
+pub const ERRNO_PROTOTYPE: u16 = 67;
+pub const ERRNO_RANGE: u16 = 68;
+pub const ERRNO_ROFS: u16 = 69;
+pub const ERRNO_SPIPE: u16 = 70;
+pub const ERRNO_SRCH: u16 = 71;
+pub const ERRNO_STALE: u16 = 72;
+pub const ERRNO_TIMEDOUT: u16 = 73;
+pub const ERRNO_TXTBSY: u16 = 74;
+pub const ERRNO_XDEV: u16 = 75;
+pub const ERRNO_NOTCAPABLE: u16 = 76;
+
+/// Converts a Linux error number to a WASI error code.
+///
+/// Returns `None` if WASI does not define an equivalent error code.
+pub const fn to_wasi(err: Error) -> Option<u16> {
+	Some(match err {
+		target::E2BIG           => ERRNO_2BIG,
+		target::EACCES          => ERRNO_ACCES,
+		target::EADDRINUSE      => ERRNO_ADDRINUSE,
+		target::EADDRNOTAVAIL   => ERRNO_ADDRNOTAVAIL,
+		target::EAFNOSUPPORT    => ERRNO_AFNOSUPPORT,
+		target::EAGAIN          => ERRNO_AGAIN,
+		target::EALREADY        => ERRNO_ALREADY,
+		target::EBADF           => ERRNO_BADF,
+		target::EBADMSG         => ERRNO_BADMSG,
+		target::EBUSY           => ERRNO_BUSY,
+		target::ECANCELED       => ERRNO_CANCELED,
+		target::ECHILD          => ERRNO_CHILD,
+		target::ECONNABORTED    => ERRNO_CONNABORTED,
+		target::ECONNREFUSED    => ERRNO_CONNREFUSED,
+		target::ECONNRESET      => ERRNO_CONNRESET,
+		target::EDEADLK         => ERRNO_DEADLK,
+		target::EDESTADDRREQ    => ERRNO_DESTADDRREQ,
+		target::EDOM            => ERRNO_DOM,
+		target::EDQUOT          => ERRNO_DQUOT,
+		target::EEXIST          => ERRNO_EXIST,
+		target::EFAULT          => ERRNO_FAULT,
+		target::EFBIG           => ERRNO_FBIG,
+		target::EHOSTUNREACH    => ERRNO_HOSTUNREACH,
+		target::EIDRM           => ERRNO_IDRM,
+		target::EILSEQ          => ERRNO_ILSEQ,
+		target::EINPROGRESS     => ERRNO_INPROGRESS,
+		target::EINTR           => ERRNO_INTR,
+		target::EINVAL          => ERRNO_INVAL,
+		target::EIO             => ERRNO_IO,
+		target::EISCONN         => ERRNO_ISCONN,
+		target::EISDIR          => ERRNO_ISDIR,
+		target::ELOOP           => ERRNO_LOOP,
+		target::EMFILE          => ERRNO_MFILE,
+		target::EMLINK          => ERRNO_MLINK,
+		target::EMSGSIZE        => ERRNO_MSGSIZE,
+		target::EMULTIHOP       => ERRNO_MULTIHOP,
+		target::ENAMETOOLONG    => ERRNO_NAMETOOLONG,
+		target::ENETDOWN        => ERRNO_NETDOWN,
+		target::ENETRESET       => ERRNO_NETRESET,
+		target::ENETUNREACH     => ERRNO_NETUNREACH,
+		target::ENFILE          => ERRNO_NFILE,
+		target::ENOBUFS         => ERRNO_NOBUFS,
+		target::ENODEV          => ERRNO_NODEV,
+		target::ENOENT          => ERRNO_NOENT,
+		target::ENOEXEC         => ERRNO_NOEXEC,
+		target::ENOLCK          => ERRNO_NOLCK,
+		target::ENOLINK         => ERRNO_NOLINK,
+		target::ENOMEM          => ERRNO_NOMEM,
+		target::ENOMSG          => ERRNO_NOMSG,
+		target::ENOPROTOOPT     => ERRNO_NOPROTOOPT,
+		target::ENOSPC          => ERRNO_NOSPC,
+		target::ENOSYS          => ERRNO_NOSYS,
+		target::ENOTCONN        => ERRNO_NOTCONN,
+		target::ENOTDIR         => ERRNO_NOTDIR,
+		target::ENOTEMPTY       => ERRNO_NOTEMPTY,
+		target::ENOTRECOVERABLE => ERRNO_NOTRECOVERABLE,
+		target::ENOTSOCK        => ERRNO_NOTSOCK,
+		target::EOPNOTSUPP      => ERRNO_NOTSUP,
+		target::ENOTTY          => ERRNO_NOTTY,
+		target::ENXIO           => ERRNO_NXIO,
+		target::EOVERFLOW       => ERRNO_OVERFLOW,
+		target::EOWNERDEAD      => ERRNO_OWNERDEAD,
+		target::EPERM           => ERRNO_PERM,
+		target::EPIPE           => ERRNO_PIPE,
+		target::EPROTO          => ERRNO_PROTO,
+		target::EPROTONOSUPPORT => ERRNO_PROTONOSUPPORT,
+		target::EPROTOTYPE      => ERRNO_PROTOTYPE,
+		target::ERANGE          => ERRNO_RANGE,
+		target::EROFS           => ERRNO_ROFS,
+		target::ESPIPE          => ERRNO_SPIPE,
+		target::ESRCH           => ERRNO_SRCH,
+		target::ESTALE          => ERRNO_STALE,
+		target::ETIMEDOUT       => ERRNO_TIMEDOUT,
+		target::ETXTBSY         => ERRNO_TXTBSY,
+		target::EXDEV           => ERRNO_XDEV,
+		_ => return None,
+	})
+}
+
+/// Converts a Linux error number to a WASI error code, substituting the
+/// closest WASI error code for Linux-specific error numbers.
+///
+/// | Linux                                  | WASI                |
+/// |----------------------------------------|---------------------|
+/// | `EBADFD`                               | `ERRNO_BADF`        |
+/// | `EHOSTDOWN`                            | `ERRNO_HOSTUNREACH` |
+/// | `ENONET`, `ERFKILL`                    | `ERRNO_NETDOWN`     |
+/// | `ENOTBLK`, `EBADE`, `EBADR`, `EBADRQC` | `ERRNO_INVAL`       |
+/// | `EBADSLT`, `EBFONT`                    | `ERRNO_INVAL`       |
+/// | `ENOSTR`                               | `ERRNO_NOTTY`       |
+/// | `ENODATA`                              | `ERRNO_NOMSG`       |
+/// | `ETIME`                                | `ERRNO_TIMEDOUT`    |
+/// | `ENOSR`                                | `ERRNO_NOBUFS`      |
+/// | `EXFULL`                               | `ERRNO_NOSPC`       |
+/// | `ENOPKG`                               | `ERRNO_NOSYS`       |
+/// | `ENOTUNIQ`                             | `ERRNO_EXIST`       |
+/// | `ELIBACC`, `ELIBBAD`, `ELIBSCN`        | `ERRNO_NOEXEC`      |
+/// | `ELIBMAX`, `ELIBEXEC`                  | `ERRNO_NOEXEC`      |
+/// | `ERESTART`                             | `ERRNO_INTR`        |
+/// | `ESTRPIPE`, `ESHUTDOWN`                | `ERRNO_PIPE`        |
+/// | `EUSERS`                               | `ERRNO_AGAIN`       |
+/// | `ESOCKTNOSUPPORT`                      | `ERRNO_NOTSUP`      |
+/// | `EPFNOSUPPORT`                         | `ERRNO_AFNOSUPPORT` |
+/// | `ENOMEDIUM`, `EMEDIUMTYPE`             | `ERRNO_NODEV`       |
+/// | `ENOKEY`, `EKEYEXPIRED`                | `ERRNO_ACCES`       |
+/// | `EKEYREVOKED`, `EKEYREJECTED`          | `ERRNO_ACCES`       |
+/// | Others, including `EHWPOISON`          | `ERRNO_IO`          |
+pub const fn to_wasi_lossy(err: Error) -> u16 {
+	if let Some(code) = to_wasi(err) {
+		return code;
+	}
+	match err {
+		target::EBADFD          => ERRNO_BADF,
+		target::EHOSTDOWN       => ERRNO_HOSTUNREACH,
+		target::ENONET          => ERRNO_NETDOWN,
+		target::ERFKILL         => ERRNO_NETDOWN,
+		target::ENOTBLK         => ERRNO_INVAL,
+		target::EBADE           => ERRNO_INVAL,
+		target::EBADR           => ERRNO_INVAL,
+		target::EBADRQC         => ERRNO_INVAL,
+		target::EBADSLT         => ERRNO_INVAL,
+		target::EBFONT          => ERRNO_INVAL,
+		target::ENOSTR          => ERRNO_NOTTY,
+		target::ENODATA         => ERRNO_NOMSG,
+		target::ETIME           => ERRNO_TIMEDOUT,
+		target::ENOSR           => ERRNO_NOBUFS,
+		target::EXFULL          => ERRNO_NOSPC,
+		target::ENOPKG          => ERRNO_NOSYS,
+		target::ENOTUNIQ        => ERRNO_EXIST,
+		target::ELIBACC         => ERRNO_NOEXEC,
+		target::ELIBBAD         => ERRNO_NOEXEC,
+		target::ELIBSCN         => ERRNO_NOEXEC,
+		target::ELIBMAX         => ERRNO_NOEXEC,
+		target::ELIBEXEC        => ERRNO_NOEXEC,
+		target::ERESTART        => ERRNO_INTR,
+		target::ESTRPIPE        => ERRNO_PIPE,
+		target::ESHUTDOWN       => ERRNO_PIPE,
+		target::EUSERS          => ERRNO_AGAIN,
+		target::ESOCKTNOSUPPORT => ERRNO_NOTSUP,
+		target::EPFNOSUPPORT    => ERRNO_AFNOSUPPORT,
+		target::ENOMEDIUM       => ERRNO_NODEV,
+		target::EMEDIUMTYPE     => ERRNO_NODEV,
+		target::ENOKEY          => ERRNO_ACCES,
+		target::EKEYEXPIRED     => ERRNO_ACCES,
+		target::EKEYREVOKED     => ERRNO_ACCES,
+		target::EKEYREJECTED    => ERRNO_ACCES,
+		_ => ERRNO_IO,
+	}
+}
+
+/// Converts a WASI error code to a Linux error number.
+///
+/// `ERRNO_NOTCAPABLE` is converted to `EPERM`. Returns `None` for
+/// `ERRNO_SUCCESS` and for values not defined by WASI.
+pub const fn from_wasi(code: u16) -> Option<Error> {
+	Some(match code {
+		ERRNO_2BIG           => target::E2BIG,
+		ERRNO_ACCES          => target::EACCES,
+		ERRNO_ADDRINUSE      => target::EADDRINUSE,
+		ERRNO_ADDRNOTAVAIL   => target::EADDRNOTAVAIL,
+		ERRNO_AFNOSUPPORT    => target::EAFNOSUPPORT,
+		ERRNO_AGAIN          => target::EAGAIN,
+		ERRNO_ALREADY        => target::EALREADY,
+		ERRNO_BADF           => target::EBADF,
+		ERRNO_BADMSG         => target::EBADMSG,
+		ERRNO_BUSY           => target::EBUSY,
+		ERRNO_CANCELED       => target::ECANCELED,
+		ERRNO_CHILD          => target::ECHILD,
+		ERRNO_CONNABORTED    => target::ECONNABORTED,
+		ERRNO_CONNREFUSED    => target::ECONNREFUSED,
+		ERRNO_CONNRESET      => target::ECONNRESET,
+		ERRNO_DEADLK         => target::EDEADLK,
+		ERRNO_DESTADDRREQ    => target::EDESTADDRREQ,
+		ERRNO_DOM            => target::EDOM,
+		ERRNO_DQUOT          => target::EDQUOT,
+		ERRNO_EXIST          => target::EEXIST,
+		ERRNO_FAULT          => target::EFAULT,
+		ERRNO_FBIG           => target::EFBIG,
+		ERRNO_HOSTUNREACH    => target::EHOSTUNREACH,
+		ERRNO_IDRM           => target::EIDRM,
+		ERRNO_ILSEQ          => target::EILSEQ,
+		ERRNO_INPROGRESS     => target::EINPROGRESS,
+		ERRNO_INTR           => target::EINTR,
+		ERRNO_INVAL          => target::EINVAL,
+		ERRNO_IO             => target::EIO,
+		ERRNO_ISCONN         => target::EISCONN,
+		ERRNO_ISDIR          => target::EISDIR,
+		ERRNO_LOOP           => target::ELOOP,
+		ERRNO_MFILE          => target::EMFILE,
+		ERRNO_MLINK          => target::EMLINK,
+		ERRNO_MSGSIZE        => target::EMSGSIZE,
+		ERRNO_MULTIHOP       => target::EMULTIHOP,
+		ERRNO_NAMETOOLONG    => target::ENAMETOOLONG,
+		ERRNO_NETDOWN        => target::ENETDOWN,
+		ERRNO_NETRESET       => target::ENETRESET,
+		ERRNO_NETUNREACH     => target::ENETUNREACH,
+		ERRNO_NFILE          => target::ENFILE,
+		ERRNO_NOBUFS         => target::ENOBUFS,
+		ERRNO_NODEV          => target::ENODEV,
+		ERRNO_NOENT          => target::ENOENT,
+		ERRNO_NOEXEC         => target::ENOEXEC,
+		ERRNO_NOLCK          => target::ENOLCK,
+		ERRNO_NOLINK         => target::ENOLINK,
+		ERRNO_NOMEM          => target::ENOMEM,
+		ERRNO_NOMSG          => target::ENOMSG,
+		ERRNO_NOPROTOOPT     => target::ENOPROTOOPT,
+		ERRNO_NOSPC          => target::ENOSPC,
+		ERRNO_NOSYS          => target::ENOSYS,
+		ERRNO_NOTCONN        => target::ENOTCONN,
+		ERRNO_NOTDIR         => target::ENOTDIR,
+		ERRNO_NOTEMPTY       => target::ENOTEMPTY,
+		ERRNO_NOTRECOVERABLE => target::ENOTRECOVERABLE,
+		ERRNO_NOTSOCK        => target::ENOTSOCK,
+		ERRNO_NOTSUP         => target::EOPNOTSUPP,
+		ERRNO_NOTTY          => target::ENOTTY,
+		ERRNO_NXIO           => target::ENXIO,
+		ERRNO_OVERFLOW       => target::EOVERFLOW,
+		ERRNO_OWNERDEAD      => target::EOWNERDEAD,
+		ERRNO_PERM           => target::EPERM,
+		ERRNO_PIPE           => target::EPIPE,
+		ERRNO_PROTO          => target::EPROTO,
+		ERRNO_PROTONOSUPPORT => target::EPROTONOSUPPORT,
+		ERRNO_PROTOTYPE      => target::EPROTOTYPE,
+		ERRNO_RANGE          => target::ERANGE,
+		ERRNO_ROFS           => target::EROFS,
+		ERRNO_SPIPE          => target::ESPIPE,
+		ERRNO_SRCH           => target::ESRCH,
+		ERRNO_STALE          => target::ESTALE,
+		ERRNO_TIMEDOUT       => target::ETIMEDOUT,
+		ERRNO_TXTBSY         => target::ETXTBSY,
+		ERRNO_XDEV           => target::EXDEV,
+		ERRNO_NOTCAPABLE     => target::EPERM,
+		_ => return None,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn wasi_round_trip() {
+		for code in ERRNO_2BIG..=ERRNO_XDEV {
+			let err = from_wasi(code).unwrap();
+			assert_eq!(to_wasi(err), Some(code), "{:?}", err);
+			assert_eq!(to_wasi_lossy(err), code, "{:?}", err);
+		}
+	}
+
+	#[test]
+	fn linux_round_trip() {
+		for err in (1..0x1000).filter_map(Error::new) {
+			if let Some(code) = to_wasi(err) {
+				assert_eq!(from_wasi(code), Some(err), "{:?}", err);
+			}
+			let code = to_wasi_lossy(err);
+			assert!(from_wasi(code).is_some(), "{:?}", err);
+		}
+	}
+
+	#[test]
+	fn not_capable() {
+		assert_eq!(from_wasi(ERRNO_NOTCAPABLE), Some(target::EPERM));
+		assert_eq!(to_wasi(target::EPERM), Some(ERRNO_PERM));
+	}
+
+	#[test]
+	fn lossy_fallbacks() {
+		assert_eq!(to_wasi(target::EHWPOISON), None);
+		assert_eq!(to_wasi_lossy(target::EHWPOISON), ERRNO_IO);
+		assert_eq!(to_wasi(target::ERFKILL), None);
+		assert_eq!(to_wasi_lossy(target::ERFKILL), ERRNO_NETDOWN);
+	}
+
+	#[test]
+	fn undefined_codes() {
+		assert_eq!(from_wasi(0), None);
+		assert_eq!(from_wasi(ERRNO_NOTCAPABLE + 1), None);
+		assert_eq!(from_wasi(u16::MAX), None);
+	}
+}
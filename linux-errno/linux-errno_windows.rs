@@ -0,0 +1,732 @@
+// Copyright (c) 2022 John Millikin <john@john-millikin.com>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+// REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+// AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+// INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+// LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+// OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+// PERFORMANCE OF THIS SOFTWARE.
+//
+// SPDX-License-Identifier: 0BSD
+
+//! Conversions between Linux error numbers and Windows error codes.
+//!
+//! Three families of Windows error codes are supported:
+//!
+//! * Win32 error codes (`ERROR_*`), as returned by `GetLastError()`.
+//! * Windows Sockets error codes (`WSAE*`), as returned by
+//!   `WSAGetLastError()`.
+//! * `NTSTATUS` values (`STATUS_*`), as returned by the NT native API and
+//!   carried in SMB2 responses.
+//!
+//! The mappings are lossy in both directions. Many Windows codes have no
+//! Linux equivalent (and vice versa), and several codes on one side collapse
+//! into a single code on the other. The tables are based on the mappings used
+//! by the Microsoft C runtime (`_dosmaperr`), Wine, and the Linux kernel's
+//! CIFS client.
+//!
+//! The Windows codes used by the conversions are provided as constants, such
+//! as [`ERROR_FILE_NOT_FOUND`], [`WSAECONNRESET`], and
+//! [`STATUS_OBJECT_NAME_NOT_FOUND`].
+//!
+//! Converting a Linux error number to a Windows code and back returns the
+//! original error number, except where several Linux error numbers share a
+//! Windows code:
+//!
+//! * `ENFILE` converts to the same code as `EMFILE`.
+//! * `ENXIO` converts to the same Win32 code as `ENODEV`, and `ETIME` to the
+//!   same Win32 code as `ETIMEDOUT`.
+//! * `ETXTBSY` converts to `ERROR_SHARING_VIOLATION`, which the C runtime
+//!   converts back to `EACCES`.
+//!
+//! Linux error numbers are interpreted according to the current target
+//! architecture.
+
+use crate::Error;
+use crate::target;
+
+// https://learn.microsoft.com/en-us/windows/win32/debug/system-error-codes
+
+pub const ERROR_INVALID_FUNCTION: u32 = 1;
+pub const ERROR_FILE_NOT_FOUND: u32 = 2;
+pub const ERROR_PATH_NOT_FOUND: u32 = 3;
+pub const ERROR_TOO_MANY_OPEN_FILES: u32 = 4;
+pub const ERROR_ACCESS_DENIED: u32 = 5;
+pub const ERROR_INVALID_HANDLE: u32 = 6;
+pub const ERROR_ARENA_TRASHED: u32 = 7;
+pub const ERROR_NOT_ENOUGH_MEMORY: u32 = 8;
+pub const ERROR_INVALID_BLOCK: u32 = 9;
+pub const ERROR_BAD_ENVIRONMENT: u32 = 10;
+pub const ERROR_BAD_FORMAT: u32 = 11;
+pub const ERROR_INVALID_ACCESS: u32 = 12;
+pub const ERROR_INVALID_DATA: u32 = 13;
+pub const ERROR_OUTOFMEMORY: u32 = 14;
+pub const ERROR_INVALID_DRIVE: u32 = 15;
+pub const ERROR_CURRENT_DIRECTORY: u32 = 16;
+pub const ERROR_NOT_SAME_DEVICE: u32 = 17;
+pub const ERROR_NO_MORE_FILES: u32 = 18;
+pub const ERROR_WRITE_PROTECT: u32 = 19;
+pub const ERROR_NOT_READY: u32 = 21;
+pub const ERROR_SHARING_VIOLATION: u32 = 32;
+pub const ERROR_LOCK_VIOLATION: u32 = 33;
+pub const ERROR_HANDLE_DISK_FULL: u32 = 39;
+pub const ERROR_NOT_SUPPORTED: u32 = 50;
+pub const ERROR_BAD_NETPATH: u32 = 53;
+pub const ERROR_DEV_NOT_EXIST: u32 = 55;
+pub const ERROR_UNEXP_NET_ERR: u32 = 59;
+pub const ERROR_NETWORK_ACCESS_DENIED: u32 = 65;
+pub const ERROR_BAD_NET_NAME: u32 = 67;
+pub const ERROR_FILE_EXISTS: u32 = 80;
+pub const ERROR_CANNOT_MAKE: u32 = 82;
+pub const ERROR_INVALID_PARAMETER: u32 = 87;
+pub const ERROR_NO_PROC_SLOTS: u32 = 89;
+pub const ERROR_BROKEN_PIPE: u32 = 109;
+pub const ERROR_DISK_FULL: u32 = 112;
+pub const ERROR_CALL_NOT_IMPLEMENTED: u32 = 120;
+pub const ERROR_SEM_TIMEOUT: u32 = 121;
+pub const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+pub const ERROR_INVALID_NAME: u32 = 123;
+pub const ERROR_WAIT_NO_CHILDREN: u32 = 128;
+pub const ERROR_CHILD_NOT_COMPLETE: u32 = 129;
+pub const ERROR_NEGATIVE_SEEK: u32 = 131;
+pub const ERROR_SEEK_ON_DEVICE: u32 = 132;
+pub const ERROR_DIR_NOT_EMPTY: u32 = 145;
+pub const ERROR_NOT_LOCKED: u32 = 158;
+pub const ERROR_BAD_PATHNAME: u32 = 161;
+pub const ERROR_MAX_THRDS_REACHED: u32 = 164;
+pub const ERROR_LOCK_FAILED: u32 = 167;
+pub const ERROR_BUSY: u32 = 170;
+pub const ERROR_ALREADY_EXISTS: u32 = 183;
+pub const ERROR_BAD_EXE_FORMAT: u32 = 193;
+pub const ERROR_FILENAME_EXCED_RANGE: u32 = 206;
+pub const ERROR_FILE_TOO_LARGE: u32 = 223;
+pub const ERROR_NO_DATA: u32 = 232;
+pub const ERROR_PIPE_NOT_CONNECTED: u32 = 233;
+pub const ERROR_DIRECTORY: u32 = 267;
+pub const ERROR_DIRECTORY_NOT_SUPPORTED: u32 = 336;
+pub const ERROR_ARITHMETIC_OVERFLOW: u32 = 534;
+pub const ERROR_OPERATION_ABORTED: u32 = 995;
+pub const ERROR_NOACCESS: u32 = 998;
+pub const ERROR_NO_MEDIA_IN_DRIVE: u32 = 1112;
+pub const ERROR_NO_UNICODE_TRANSLATION: u32 = 1113;
+pub const ERROR_IO_DEVICE: u32 = 1117;
+pub const ERROR_POSSIBLE_DEADLOCK: u32 = 1131;
+pub const ERROR_TOO_MANY_LINKS: u32 = 1142;
+pub const ERROR_NOT_FOUND: u32 = 1168;
+pub const ERROR_CANCELLED: u32 = 1223;
+pub const ERROR_CONNECTION_REFUSED: u32 = 1225;
+pub const ERROR_NETWORK_UNREACHABLE: u32 = 1231;
+pub const ERROR_HOST_UNREACHABLE: u32 = 1232;
+pub const ERROR_CONNECTION_ABORTED: u32 = 1236;
+pub const ERROR_RETRY: u32 = 1237;
+pub const ERROR_DISK_QUOTA_EXCEEDED: u32 = 1295;
+pub const ERROR_PRIVILEGE_NOT_HELD: u32 = 1314;
+pub const ERROR_FILE_CORRUPT: u32 = 1392;
+pub const ERROR_NO_SYSTEM_RESOURCES: u32 = 1450;
+pub const ERROR_TIMEOUT: u32 = 1460;
+pub const ERROR_NOT_ENOUGH_QUOTA: u32 = 1816;
+pub const ERROR_CANT_RESOLVE_FILENAME: u32 = 1921;
+
+// https://learn.microsoft.com/en-us/windows/win32/winsock/windows-sockets-error-codes-2
+
+pub const WSAEINTR: i32 = 10004;
+pub const WSAEBADF: i32 = 10009;
+pub const WSAEACCES: i32 = 10013;
+pub const WSAEFAULT: i32 = 10014;
+pub const WSAEINVAL: i32 = 10022;
+pub const WSAEMFILE: i32 = 10024;
+pub const WSAEWOULDBLOCK: i32 = 10035;
+pub const WSAEINPROGRESS: i32 = 10036;
+pub const WSAEALREADY: i32 = 10037;
+pub const WSAENOTSOCK: i32 = 10038;
+pub const WSAEDESTADDRREQ: i32 = 10039;
+pub const WSAEMSGSIZE: i32 = 10040;
+pub const WSAEPROTOTYPE: i32 = 10041;
+pub const WSAENOPROTOOPT: i32 = 10042;
+pub const WSAEPROTONOSUPPORT: i32 = 10043;
+pub const WSAESOCKTNOSUPPORT: i32 = 10044;
+pub const WSAEOPNOTSUPP: i32 = 10045;
+pub const WSAEPFNOSUPPORT: i32 = 10046;
+pub const WSAEAFNOSUPPORT: i32 = 10047;
+pub const WSAEADDRINUSE: i32 = 10048;
+pub const WSAEADDRNOTAVAIL: i32 = 10049;
+pub const WSAENETDOWN: i32 = 10050;
+pub const WSAENETUNREACH: i32 = 10051;
+pub const WSAENETRESET: i32 = 10052;
+pub const WSAECONNABORTED: i32 = 10053;
+pub const WSAECONNRESET: i32 = 10054;
+pub const WSAENOBUFS: i32 = 10055;
+pub const WSAEISCONN: i32 = 10056;
+pub const WSAENOTCONN: i32 = 10057;
+pub const WSAESHUTDOWN: i32 = 10058;
+pub const WSAETOOMANYREFS: i32 = 10059;
+pub const WSAETIMEDOUT: i32 = 10060;
+pub const WSAECONNREFUSED: i32 = 10061;
+pub const WSAELOOP: i32 = 10062;
+pub const WSAENAMETOOLONG: i32 = 10063;
+pub const WSAEHOSTDOWN: i32 = 10064;
+pub const WSAEHOSTUNREACH: i32 = 10065;
+pub const WSAENOTEMPTY: i32 = 10066;
+pub const WSAEUSERS: i32 = 10068;
+pub const WSAEDQUOT: i32 = 10069;
+pub const WSAESTALE: i32 = 10070;
+pub const WSAEREMOTE: i32 = 10071;
+pub const WSAECANCELLED: i32 = 10103;
+
+// https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-erref/596a1078-e883-4972-9bbc-49e60bebca55
+
+pub const STATUS_DEVICE_BUSY: u32 = 0x80000011;
+pub const STATUS_NOT_IMPLEMENTED: u32 = 0xC0000002;
+pub const STATUS_ACCESS_VIOLATION: u32 = 0xC0000005;
+pub const STATUS_INVALID_HANDLE: u32 = 0xC0000008;
+pub const STATUS_INVALID_PARAMETER: u32 = 0xC000000D;
+pub const STATUS_NO_SUCH_DEVICE: u32 = 0xC000000E;
+pub const STATUS_NO_SUCH_FILE: u32 = 0xC000000F;
+pub const STATUS_INVALID_DEVICE_REQUEST: u32 = 0xC0000010;
+pub const STATUS_END_OF_FILE: u32 = 0xC0000011;
+pub const STATUS_NO_MEDIA_IN_DEVICE: u32 = 0xC0000013;
+pub const STATUS_NO_MEMORY: u32 = 0xC0000017;
+pub const STATUS_ACCESS_DENIED: u32 = 0xC0000022;
+pub const STATUS_BUFFER_TOO_SMALL: u32 = 0xC0000023;
+pub const STATUS_OBJECT_NAME_INVALID: u32 = 0xC0000033;
+pub const STATUS_OBJECT_NAME_NOT_FOUND: u32 = 0xC0000034;
+pub const STATUS_OBJECT_NAME_COLLISION: u32 = 0xC0000035;
+pub const STATUS_OBJECT_PATH_INVALID: u32 = 0xC0000039;
+pub const STATUS_OBJECT_PATH_NOT_FOUND: u32 = 0xC000003A;
+pub const STATUS_OBJECT_PATH_SYNTAX_BAD: u32 = 0xC000003B;
+pub const STATUS_DATA_ERROR: u32 = 0xC000003E;
+pub const STATUS_SHARING_VIOLATION: u32 = 0xC0000043;
+pub const STATUS_QUOTA_EXCEEDED: u32 = 0xC0000044;
+pub const STATUS_NO_EAS_ON_FILE: u32 = 0xC0000052;
+pub const STATUS_FILE_LOCK_CONFLICT: u32 = 0xC0000054;
+pub const STATUS_LOCK_NOT_GRANTED: u32 = 0xC0000055;
+pub const STATUS_DELETE_PENDING: u32 = 0xC0000056;
+pub const STATUS_PRIVILEGE_NOT_HELD: u32 = 0xC0000061;
+pub const STATUS_WRONG_PASSWORD: u32 = 0xC000006A;
+pub const STATUS_LOGON_FAILURE: u32 = 0xC000006D;
+pub const STATUS_PASSWORD_EXPIRED: u32 = 0xC0000071;
+pub const STATUS_INVALID_IMAGE_FORMAT: u32 = 0xC000007B;
+pub const STATUS_DISK_FULL: u32 = 0xC000007F;
+pub const STATUS_INTEGER_OVERFLOW: u32 = 0xC0000095;
+pub const STATUS_INSUFFICIENT_RESOURCES: u32 = 0xC000009A;
+pub const STATUS_MEDIA_WRITE_PROTECTED: u32 = 0xC00000A2;
+pub const STATUS_ILLEGAL_FUNCTION: u32 = 0xC00000AF;
+pub const STATUS_PIPE_DISCONNECTED: u32 = 0xC00000B0;
+pub const STATUS_IO_TIMEOUT: u32 = 0xC00000B5;
+pub const STATUS_FILE_IS_A_DIRECTORY: u32 = 0xC00000BA;
+pub const STATUS_NOT_SUPPORTED: u32 = 0xC00000BB;
+pub const STATUS_BAD_NETWORK_PATH: u32 = 0xC00000BE;
+pub const STATUS_DEVICE_DOES_NOT_EXIST: u32 = 0xC00000C0;
+pub const STATUS_UNEXPECTED_NETWORK_ERROR: u32 = 0xC00000C4;
+pub const STATUS_BAD_NETWORK_NAME: u32 = 0xC00000CC;
+pub const STATUS_NOT_SAME_DEVICE: u32 = 0xC00000D4;
+pub const STATUS_UNEXPECTED_IO_ERROR: u32 = 0xC00000E9;
+pub const STATUS_DIRECTORY_NOT_EMPTY: u32 = 0xC0000101;
+pub const STATUS_FILE_CORRUPT_ERROR: u32 = 0xC0000102;
+pub const STATUS_NOT_A_DIRECTORY: u32 = 0xC0000103;
+pub const STATUS_NAME_TOO_LONG: u32 = 0xC0000106;
+pub const STATUS_TOO_MANY_OPENED_FILES: u32 = 0xC000011F;
+pub const STATUS_CANCELLED: u32 = 0xC0000120;
+pub const STATUS_CANNOT_DELETE: u32 = 0xC0000121;
+pub const STATUS_FILE_CLOSED: u32 = 0xC0000128;
+pub const STATUS_INVALID_ADDRESS: u32 = 0xC0000141;
+pub const STATUS_PIPE_BROKEN: u32 = 0xC000014B;
+pub const STATUS_UNMAPPABLE_CHARACTER: u32 = 0xC0000162;
+pub const STATUS_POSSIBLE_DEADLOCK: u32 = 0xC0000194;
+pub const STATUS_ADDRESS_ALREADY_EXISTS: u32 = 0xC000020A;
+pub const STATUS_CONNECTION_DISCONNECTED: u32 = 0xC000020C;
+pub const STATUS_CONNECTION_RESET: u32 = 0xC000020D;
+pub const STATUS_NOT_FOUND: u32 = 0xC0000225;
+pub const STATUS_RETRY: u32 = 0xC000022D;
+pub const STATUS_CONNECTION_REFUSED: u32 = 0xC0000236;
+pub const STATUS_NETWORK_UNREACHABLE: u32 = 0xC000023C;
+pub const STATUS_HOST_UNREACHABLE: u32 = 0xC000023D;
+pub const STATUS_CONNECTION_ABORTED: u32 = 0xC0000241;
+pub const STATUS_PATH_NOT_COVERED: u32 = 0xC0000257;
+pub const STATUS_TOO_MANY_LINKS: u32 = 0xC0000265;
+pub const STATUS_IO_REPARSE_TAG_NOT_HANDLED: u32 = 0xC0000279;
+pub const STATUS_REPARSE_POINT_NOT_RESOLVED: u32 = 0xC0000280;
+pub const STATUS_FILE_TOO_LARGE: u32 = 0xC0000904;
+
+/// Converts a Linux error number to a Win32 error code.
+///
+/// Error numbers for socket operations are converted to the corresponding
+/// Windows Sockets error code (see [`to_wsa`]), which Windows also uses as
+/// the thread's last error. Returns `None` if there is no reasonable
+/// equivalent.
+pub const fn to_win32(err: Error) -> Option<u32> {
+	Some(match err {
+		target::EPERM        => ERROR_PRIVILEGE_NOT_HELD,
+		target::ENOENT       => ERROR_FILE_NOT_FOUND,
+		target::EIO          => ERROR_IO_DEVICE,
+		target::ENXIO        => ERROR_DEV_NOT_EXIST,
+		target::E2BIG        => ERROR_BAD_ENVIRONMENT,
+		target::ENOEXEC      => ERROR_BAD_EXE_FORMAT,
+		target::EBADF        => ERROR_INVALID_HANDLE,
+		target::ECHILD       => ERROR_WAIT_NO_CHILDREN,
+		target::EAGAIN       => ERROR_RETRY,
+		target::ENOMEM       => ERROR_NOT_ENOUGH_MEMORY,
+		target::EACCES       => ERROR_ACCESS_DENIED,
+		target::EFAULT       => ERROR_NOACCESS,
+		target::EBUSY        => ERROR_BUSY,
+		target::EEXIST       => ERROR_ALREADY_EXISTS,
+		target::EXDEV        => ERROR_NOT_SAME_DEVICE,
+		target::ENODEV       => ERROR_DEV_NOT_EXIST,
+		target::ENOTDIR      => ERROR_DIRECTORY,
+		target::EISDIR       => ERROR_DIRECTORY_NOT_SUPPORTED,
+		target::EINVAL       => ERROR_INVALID_PARAMETER,
+		target::ENFILE       => ERROR_TOO_MANY_OPEN_FILES,
+		target::EMFILE       => ERROR_TOO_MANY_OPEN_FILES,
+		target::ENOTTY       => ERROR_INVALID_FUNCTION,
+		target::ETXTBSY      => ERROR_SHARING_VIOLATION,
+		target::EFBIG        => ERROR_FILE_TOO_LARGE,
+		target::ENOSPC       => ERROR_DISK_FULL,
+		target::ESPIPE       => ERROR_SEEK_ON_DEVICE,
+		target::EROFS        => ERROR_WRITE_PROTECT,
+		target::EMLINK       => ERROR_TOO_MANY_LINKS,
+		target::EPIPE        => ERROR_BROKEN_PIPE,
+		target::ERANGE       => ERROR_INSUFFICIENT_BUFFER,
+		target::EDEADLK      => ERROR_POSSIBLE_DEADLOCK,
+		target::ENAMETOOLONG => ERROR_FILENAME_EXCED_RANGE,
+		target::ENOLCK       => ERROR_LOCK_FAILED,
+		target::ENOSYS       => ERROR_CALL_NOT_IMPLEMENTED,
+		target::ENOTEMPTY    => ERROR_DIR_NOT_EMPTY,
+		target::ELOOP        => ERROR_CANT_RESOLVE_FILENAME,
+		target::ETIME        => ERROR_TIMEOUT,
+		target::ENOSR        => ERROR_NO_SYSTEM_RESOURCES,
+		target::EOVERFLOW    => ERROR_ARITHMETIC_OVERFLOW,
+		target::EILSEQ       => ERROR_NO_UNICODE_TRANSLATION,
+		target::EOPNOTSUPP   => ERROR_NOT_SUPPORTED,
+		target::ETIMEDOUT    => ERROR_TIMEOUT,
+		target::EUCLEAN      => ERROR_FILE_CORRUPT,
+		target::EREMOTEIO    => ERROR_UNEXP_NET_ERR,
+		target::EDQUOT       => ERROR_DISK_QUOTA_EXCEEDED,
+		target::ENOMEDIUM    => ERROR_NO_MEDIA_IN_DRIVE,
+		target::ECANCELED    => ERROR_OPERATION_ABORTED,
+		_ => match to_wsa(err) {
+			Some(code) => code as u32,
+			None => return None,
+		},
+	})
+}
+
+/// Converts a Win32 error code to a Linux error number.
+///
+/// Windows Sockets error codes are also accepted (see [`from_wsa`]). Returns
+/// `None` for `ERROR_SUCCESS` and for codes with no reasonable equivalent.
+pub const fn from_win32(code: u32) -> Option<Error> {
+	Some(match code {
+		ERROR_INVALID_FUNCTION        => target::ENOTTY,
+		ERROR_FILE_NOT_FOUND          => target::ENOENT,
+		ERROR_PATH_NOT_FOUND          => target::ENOENT,
+		ERROR_TOO_MANY_OPEN_FILES     => target::EMFILE,
+		ERROR_ACCESS_DENIED           => target::EACCES,
+		ERROR_INVALID_HANDLE          => target::EBADF,
+		ERROR_ARENA_TRASHED           => target::ENOMEM,
+		ERROR_NOT_ENOUGH_MEMORY       => target::ENOMEM,
+		ERROR_INVALID_BLOCK           => target::ENOMEM,
+		ERROR_BAD_ENVIRONMENT         => target::E2BIG,
+		ERROR_BAD_FORMAT              => target::ENOEXEC,
+		ERROR_INVALID_ACCESS          => target::EINVAL,
+		ERROR_INVALID_DATA            => target::EINVAL,
+		ERROR_OUTOFMEMORY             => target::ENOMEM,
+		ERROR_INVALID_DRIVE           => target::ENOENT,
+		ERROR_CURRENT_DIRECTORY       => target::EACCES,
+		ERROR_NOT_SAME_DEVICE         => target::EXDEV,
+		ERROR_NO_MORE_FILES           => target::ENOENT,
+		ERROR_WRITE_PROTECT           => target::EROFS,
+		ERROR_NOT_READY               => target::EAGAIN,
+		ERROR_SHARING_VIOLATION       => target::EACCES,
+		ERROR_LOCK_VIOLATION          => target::EACCES,
+		ERROR_HANDLE_DISK_FULL        => target::ENOSPC,
+		ERROR_NOT_SUPPORTED           => target::EOPNOTSUPP,
+		ERROR_BAD_NETPATH             => target::ENOENT,
+		ERROR_DEV_NOT_EXIST           => target::ENODEV,
+		ERROR_UNEXP_NET_ERR           => target::EREMOTEIO,
+		ERROR_NETWORK_ACCESS_DENIED   => target::EACCES,
+		ERROR_BAD_NET_NAME            => target::ENOENT,
+		ERROR_FILE_EXISTS             => target::EEXIST,
+		ERROR_CANNOT_MAKE             => target::EACCES,
+		ERROR_INVALID_PARAMETER       => target::EINVAL,
+		ERROR_NO_PROC_SLOTS           => target::EAGAIN,
+		ERROR_BROKEN_PIPE             => target::EPIPE,
+		ERROR_DISK_FULL               => target::ENOSPC,
+		ERROR_CALL_NOT_IMPLEMENTED    => target::ENOSYS,
+		ERROR_SEM_TIMEOUT             => target::ETIMEDOUT,
+		ERROR_INSUFFICIENT_BUFFER     => target::ERANGE,
+		ERROR_INVALID_NAME            => target::ENOENT,
+		ERROR_WAIT_NO_CHILDREN        => target::ECHILD,
+		ERROR_CHILD_NOT_COMPLETE      => target::ECHILD,
+		ERROR_NEGATIVE_SEEK           => target::EINVAL,
+		ERROR_SEEK_ON_DEVICE          => target::ESPIPE,
+		ERROR_DIR_NOT_EMPTY           => target::ENOTEMPTY,
+		ERROR_NOT_LOCKED              => target::EACCES,
+		ERROR_BAD_PATHNAME            => target::ENOENT,
+		ERROR_MAX_THRDS_REACHED       => target::EAGAIN,
+		ERROR_LOCK_FAILED             => target::ENOLCK,
+		ERROR_BUSY                    => target::EBUSY,
+		ERROR_ALREADY_EXISTS          => target::EEXIST,
+		ERROR_BAD_EXE_FORMAT          => target::ENOEXEC,
+		ERROR_FILENAME_EXCED_RANGE    => target::ENAMETOOLONG,
+		ERROR_FILE_TOO_LARGE          => target::EFBIG,
+		ERROR_NO_DATA                 => target::EPIPE,
+		ERROR_PIPE_NOT_CONNECTED      => target::EPIPE,
+		ERROR_DIRECTORY               => target::ENOTDIR,
+		ERROR_DIRECTORY_NOT_SUPPORTED => target::EISDIR,
+		ERROR_ARITHMETIC_OVERFLOW     => target::EOVERFLOW,
+		ERROR_OPERATION_ABORTED       => target::ECANCELED,
+		ERROR_NOACCESS                => target::EFAULT,
+		ERROR_NO_MEDIA_IN_DRIVE       => target::ENOMEDIUM,
+		ERROR_NO_UNICODE_TRANSLATION  => target::EILSEQ,
+		ERROR_IO_DEVICE               => target::EIO,
+		ERROR_POSSIBLE_DEADLOCK       => target::EDEADLK,
+		ERROR_TOO_MANY_LINKS          => target::EMLINK,
+		ERROR_NOT_FOUND               => target::ENOENT,
+		ERROR_CANCELLED               => target::ECANCELED,
+		ERROR_CONNECTION_REFUSED      => target::ECONNREFUSED,
+		ERROR_NETWORK_UNREACHABLE     => target::ENETUNREACH,
+		ERROR_HOST_UNREACHABLE        => target::EHOSTUNREACH,
+		ERROR_CONNECTION_ABORTED      => target::ECONNABORTED,
+		ERROR_RETRY                   => target::EAGAIN,
+		ERROR_DISK_QUOTA_EXCEEDED     => target::EDQUOT,
+		ERROR_PRIVILEGE_NOT_HELD      => target::EPERM,
+		ERROR_FILE_CORRUPT            => target::EUCLEAN,
+		ERROR_NO_SYSTEM_RESOURCES     => target::ENOSR,
+		ERROR_TIMEOUT                 => target::ETIMEDOUT,
+		ERROR_NOT_ENOUGH_QUOTA        => target::ENOMEM,
+		ERROR_CANT_RESOLVE_FILENAME   => target::ELOOP,
+		_ => {
+			if code > (i32::MAX as u32) {
+				return None;
+			}
+			return from_wsa(code as i32);
+		},
+	})
+}
+
+/// Converts a Linux error number to a Windows Sockets error code.
+///
+/// Returns `None` if there is no `WSAE*` code with the same meaning.
+pub const fn to_wsa(err: Error) -> Option<i32> {
+	Some(match err {
+		target::EINTR           => WSAEINTR,
+		target::EBADF           => WSAEBADF,
+		target::EACCES          => WSAEACCES,
+		target::EFAULT          => WSAEFAULT,
+		target::EINVAL          => WSAEINVAL,
+		target::EMFILE          => WSAEMFILE,
+		target::EAGAIN          => WSAEWOULDBLOCK,
+		target::EINPROGRESS     => WSAEINPROGRESS,
+		target::EALREADY        => WSAEALREADY,
+		target::ENOTSOCK        => WSAENOTSOCK,
+		target::EDESTADDRREQ    => WSAEDESTADDRREQ,
+		target::EMSGSIZE        => WSAEMSGSIZE,
+		target::EPROTOTYPE      => WSAEPROTOTYPE,
+		target::ENOPROTOOPT     => WSAENOPROTOOPT,
+		target::EPROTONOSUPPORT => WSAEPROTONOSUPPORT,
+		target::ESOCKTNOSUPPORT => WSAESOCKTNOSUPPORT,
+		target::EOPNOTSUPP      => WSAEOPNOTSUPP,
+		target::EPFNOSUPPORT    => WSAEPFNOSUPPORT,
+		target::EAFNOSUPPORT    => WSAEAFNOSUPPORT,
+		target::EADDRINUSE      => WSAEADDRINUSE,
+		target::EADDRNOTAVAIL   => WSAEADDRNOTAVAIL,
+		target::ENETDOWN        => WSAENETDOWN,
+		target::ENETUNREACH     => WSAENETUNREACH,
+		target::ENETRESET       => WSAENETRESET,
+		target::ECONNABORTED    => WSAECONNABORTED,
+		target::ECONNRESET      => WSAECONNRESET,
+		target::ENOBUFS         => WSAENOBUFS,
+		target::EISCONN         => WSAEISCONN,
+		target::ENOTCONN        => WSAENOTCONN,
+		target::ESHUTDOWN       => WSAESHUTDOWN,
+		target::ETOOMANYREFS    => WSAETOOMANYREFS,
+		target::ETIMEDOUT       => WSAETIMEDOUT,
+		target::ECONNREFUSED    => WSAECONNREFUSED,
+		target::ELOOP           => WSAELOOP,
+		target::ENAMETOOLONG    => WSAENAMETOOLONG,
+		target::EHOSTDOWN       => WSAEHOSTDOWN,
+		target::EHOSTUNREACH    => WSAEHOSTUNREACH,
+		target::ENOTEMPTY       => WSAENOTEMPTY,
+		target::EUSERS          => WSAEUSERS,
+		target::EDQUOT          => WSAEDQUOT,
+		target::ESTALE          => WSAESTALE,
+		target::EREMOTE         => WSAEREMOTE,
+		target::ECANCELED       => WSAECANCELLED,
+		_ => return None,
+	})
+}
+
+/// Converts a Windows Sockets error code to a Linux error number.
+///
+/// Returns `None` if there is no Linux error number with the same meaning.
+pub const fn from_wsa(code: i32) -> Option<Error> {
+	Some(match code {
+		WSAEINTR           => target::EINTR,
+		WSAEBADF           => target::EBADF,
+		WSAEACCES          => target::EACCES,
+		WSAEFAULT          => target::EFAULT,
+		WSAEINVAL          => target::EINVAL,
+		WSAEMFILE          => target::EMFILE,
+		WSAEWOULDBLOCK     => target::EWOULDBLOCK,
+		WSAEINPROGRESS     => target::EINPROGRESS,
+		WSAEALREADY        => target::EALREADY,
+		WSAENOTSOCK        => target::ENOTSOCK,
+		WSAEDESTADDRREQ    => target::EDESTADDRREQ,
+		WSAEMSGSIZE        => target::EMSGSIZE,
+		WSAEPROTOTYPE      => target::EPROTOTYPE,
+		WSAENOPROTOOPT     => target::ENOPROTOOPT,
+		WSAEPROTONOSUPPORT => target::EPROTONOSUPPORT,
+		WSAESOCKTNOSUPPORT => target::ESOCKTNOSUPPORT,
+		WSAEOPNOTSUPP      => target::EOPNOTSUPP,
+		WSAEPFNOSUPPORT    => target::EPFNOSUPPORT,
+		WSAEAFNOSUPPORT    => target::EAFNOSUPPORT,
+		WSAEADDRINUSE      => target::EADDRINUSE,
+		WSAEADDRNOTAVAIL   => target::EADDRNOTAVAIL,
+		WSAENETDOWN        => target::ENETDOWN,
+		WSAENETUNREACH     => target::ENETUNREACH,
+		WSAENETRESET       => target::ENETRESET,
+		WSAECONNABORTED    => target::ECONNABORTED,
+		WSAECONNRESET      => target::ECONNRESET,
+		WSAENOBUFS         => target::ENOBUFS,
+		WSAEISCONN         => target::EISCONN,
+		WSAENOTCONN        => target::ENOTCONN,
+		WSAESHUTDOWN       => target::ESHUTDOWN,
+		WSAETOOMANYREFS    => target::ETOOMANYREFS,
+		WSAETIMEDOUT       => target::ETIMEDOUT,
+		WSAECONNREFUSED    => target::ECONNREFUSED,
+		WSAELOOP           => target::ELOOP,
+		WSAENAMETOOLONG    => target::ENAMETOOLONG,
+		WSAEHOSTDOWN       => target::EHOSTDOWN,
+		WSAEHOSTUNREACH    => target::EHOSTUNREACH,
+		WSAENOTEMPTY       => target::ENOTEMPTY,
+		WSAEUSERS          => target::EUSERS,
+		WSAEDQUOT          => target::EDQUOT,
+		WSAESTALE          => target::ESTALE,
+		WSAEREMOTE         => target::EREMOTE,
+		WSAECANCELLED      => target::ECANCELED,
+		_ => return None,
+	})
+}
+
+/// Converts a Linux error number to an `NTSTATUS` value.
+///
+/// Returns `None` if there is no reasonable equivalent.
+pub const fn to_ntstatus(err: Error) -> Option<u32> {
+	Some(match err {
+		target::EPERM           => STATUS_PRIVILEGE_NOT_HELD,
+		target::ENOENT          => STATUS_OBJECT_NAME_NOT_FOUND,
+		target::EIO             => STATUS_UNEXPECTED_IO_ERROR,
+		target::ENXIO           => STATUS_NO_SUCH_DEVICE,
+		target::ENOEXEC         => STATUS_INVALID_IMAGE_FORMAT,
+		target::EBADF           => STATUS_INVALID_HANDLE,
+		target::EAGAIN          => STATUS_RETRY,
+		target::ENOMEM          => STATUS_NO_MEMORY,
+		target::EACCES          => STATUS_ACCESS_DENIED,
+		target::EFAULT          => STATUS_ACCESS_VIOLATION,
+		target::EBUSY           => STATUS_DEVICE_BUSY,
+		target::EEXIST          => STATUS_OBJECT_NAME_COLLISION,
+		target::EXDEV           => STATUS_NOT_SAME_DEVICE,
+		target::ENODEV          => STATUS_DEVICE_DOES_NOT_EXIST,
+		target::ENOTDIR         => STATUS_NOT_A_DIRECTORY,
+		target::EISDIR          => STATUS_FILE_IS_A_DIRECTORY,
+		target::EINVAL          => STATUS_INVALID_PARAMETER,
+		target::ENFILE          => STATUS_TOO_MANY_OPENED_FILES,
+		target::EMFILE          => STATUS_TOO_MANY_OPENED_FILES,
+		target::ENOTTY          => STATUS_INVALID_DEVICE_REQUEST,
+		target::EFBIG           => STATUS_FILE_TOO_LARGE,
+		target::ENOSPC          => STATUS_DISK_FULL,
+		target::ESPIPE          => STATUS_ILLEGAL_FUNCTION,
+		target::EROFS           => STATUS_MEDIA_WRITE_PROTECTED,
+		target::EMLINK          => STATUS_TOO_MANY_LINKS,
+		target::EPIPE           => STATUS_PIPE_BROKEN,
+		target::ERANGE          => STATUS_BUFFER_TOO_SMALL,
+		target::EDEADLK         => STATUS_POSSIBLE_DEADLOCK,
+		target::ENAMETOOLONG    => STATUS_NAME_TOO_LONG,
+		target::ENOLCK          => STATUS_LOCK_NOT_GRANTED,
+		target::ENOSYS          => STATUS_NOT_IMPLEMENTED,
+		target::ENOTEMPTY       => STATUS_DIRECTORY_NOT_EMPTY,
+		target::ELOOP           => STATUS_REPARSE_POINT_NOT_RESOLVED,
+		target::ENODATA         => STATUS_NO_EAS_ON_FILE,
+		target::EOVERFLOW       => STATUS_INTEGER_OVERFLOW,
+		target::EREMOTE         => STATUS_PATH_NOT_COVERED,
+		target::EILSEQ          => STATUS_UNMAPPABLE_CHARACTER,
+		target::EOPNOTSUPP      => STATUS_NOT_SUPPORTED,
+		target::EADDRINUSE      => STATUS_ADDRESS_ALREADY_EXISTS,
+		target::EADDRNOTAVAIL   => STATUS_INVALID_ADDRESS,
+		target::ENETUNREACH     => STATUS_NETWORK_UNREACHABLE,
+		target::ECONNABORTED    => STATUS_CONNECTION_ABORTED,
+		target::ECONNRESET      => STATUS_CONNECTION_RESET,
+		target::ENOBUFS         => STATUS_INSUFFICIENT_RESOURCES,
+		target::ENOTCONN        => STATUS_CONNECTION_DISCONNECTED,
+		target::ETIMEDOUT       => STATUS_IO_TIMEOUT,
+		target::ECONNREFUSED    => STATUS_CONNECTION_REFUSED,
+		target::EHOSTUNREACH    => STATUS_HOST_UNREACHABLE,
+		target::EUCLEAN         => STATUS_FILE_CORRUPT_ERROR,
+		target::EREMOTEIO       => STATUS_UNEXPECTED_NETWORK_ERROR,
+		target::EDQUOT          => STATUS_QUOTA_EXCEEDED,
+		target::ENOMEDIUM       => STATUS_NO_MEDIA_IN_DEVICE,
+		target::ECANCELED       => STATUS_CANCELLED,
+		target::EKEYEXPIRED     => STATUS_PASSWORD_EXPIRED,
+		_ => return None,
+	})
+}
+
+/// Converts an `NTSTATUS` value to a Linux error number.
+///
+/// Returns `None` for success and informational values, and for error values
+/// with no reasonable equivalent.
+pub const fn from_ntstatus(status: u32) -> Option<Error> {
+	Some(match status {
+		STATUS_DEVICE_BUSY                => target::EBUSY,
+		STATUS_NOT_IMPLEMENTED            => target::ENOSYS,
+		STATUS_ACCESS_VIOLATION           => target::EFAULT,
+		STATUS_INVALID_HANDLE             => target::EBADF,
+		STATUS_INVALID_PARAMETER          => target::EINVAL,
+		STATUS_NO_SUCH_DEVICE             => target::ENXIO,
+		STATUS_NO_SUCH_FILE               => target::ENOENT,
+		STATUS_INVALID_DEVICE_REQUEST     => target::ENOTTY,
+		STATUS_END_OF_FILE                => target::ENODATA,
+		STATUS_NO_MEDIA_IN_DEVICE         => target::ENOMEDIUM,
+		STATUS_NO_MEMORY                  => target::ENOMEM,
+		STATUS_ACCESS_DENIED              => target::EACCES,
+		STATUS_BUFFER_TOO_SMALL           => target::ERANGE,
+		STATUS_OBJECT_NAME_INVALID        => target::ENOENT,
+		STATUS_OBJECT_NAME_NOT_FOUND      => target::ENOENT,
+		STATUS_OBJECT_NAME_COLLISION      => target::EEXIST,
+		STATUS_OBJECT_PATH_INVALID        => target::ENOTDIR,
+		STATUS_OBJECT_PATH_NOT_FOUND      => target::ENOENT,
+		STATUS_OBJECT_PATH_SYNTAX_BAD     => target::ENOENT,
+		STATUS_DATA_ERROR                 => target::EIO,
+		STATUS_SHARING_VIOLATION          => target::EBUSY,
+		STATUS_QUOTA_EXCEEDED             => target::EDQUOT,
+		STATUS_NO_EAS_ON_FILE             => target::ENODATA,
+		STATUS_FILE_LOCK_CONFLICT         => target::EACCES,
+		STATUS_LOCK_NOT_GRANTED           => target::ENOLCK,
+		STATUS_DELETE_PENDING             => target::ENOENT,
+		STATUS_PRIVILEGE_NOT_HELD         => target::EPERM,
+		STATUS_WRONG_PASSWORD             => target::EACCES,
+		STATUS_LOGON_FAILURE              => target::EACCES,
+		STATUS_PASSWORD_EXPIRED           => target::EKEYEXPIRED,
+		STATUS_INVALID_IMAGE_FORMAT       => target::ENOEXEC,
+		STATUS_DISK_FULL                  => target::ENOSPC,
+		STATUS_INTEGER_OVERFLOW           => target::EOVERFLOW,
+		STATUS_INSUFFICIENT_RESOURCES     => target::ENOBUFS,
+		STATUS_MEDIA_WRITE_PROTECTED      => target::EROFS,
+		STATUS_ILLEGAL_FUNCTION           => target::ESPIPE,
+		STATUS_PIPE_DISCONNECTED          => target::EPIPE,
+		STATUS_IO_TIMEOUT                 => target::ETIMEDOUT,
+		STATUS_FILE_IS_A_DIRECTORY        => target::EISDIR,
+		STATUS_NOT_SUPPORTED              => target::EOPNOTSUPP,
+		STATUS_BAD_NETWORK_PATH           => target::ENOENT,
+		STATUS_DEVICE_DOES_NOT_EXIST      => target::ENODEV,
+		STATUS_UNEXPECTED_NETWORK_ERROR   => target::EREMOTEIO,
+		STATUS_BAD_NETWORK_NAME           => target::ENOENT,
+		STATUS_NOT_SAME_DEVICE            => target::EXDEV,
+		STATUS_UNEXPECTED_IO_ERROR        => target::EIO,
+		STATUS_DIRECTORY_NOT_EMPTY        => target::ENOTEMPTY,
+		STATUS_FILE_CORRUPT_ERROR         => target::EUCLEAN,
+		STATUS_NOT_A_DIRECTORY            => target::ENOTDIR,
+		STATUS_NAME_TOO_LONG              => target::ENAMETOOLONG,
+		STATUS_TOO_MANY_OPENED_FILES      => target::EMFILE,
+		STATUS_CANCELLED                  => target::ECANCELED,
+		STATUS_CANNOT_DELETE              => target::EACCES,
+		STATUS_FILE_CLOSED                => target::EBADF,
+		STATUS_INVALID_ADDRESS            => target::EADDRNOTAVAIL,
+		STATUS_PIPE_BROKEN                => target::EPIPE,
+		STATUS_UNMAPPABLE_CHARACTER       => target::EILSEQ,
+		STATUS_POSSIBLE_DEADLOCK          => target::EDEADLK,
+		STATUS_ADDRESS_ALREADY_EXISTS     => target::EADDRINUSE,
+		STATUS_CONNECTION_DISCONNECTED    => target::ENOTCONN,
+		STATUS_CONNECTION_RESET           => target::ECONNRESET,
+		STATUS_NOT_FOUND                  => target::ENOENT,
+		STATUS_RETRY                      => target::EAGAIN,
+		STATUS_CONNECTION_REFUSED         => target::ECONNREFUSED,
+		STATUS_NETWORK_UNREACHABLE        => target::ENETUNREACH,
+		STATUS_HOST_UNREACHABLE           => target::EHOSTUNREACH,
+		STATUS_CONNECTION_ABORTED         => target::ECONNABORTED,
+		STATUS_PATH_NOT_COVERED           => target::EREMOTE,
+		STATUS_TOO_MANY_LINKS             => target::EMLINK,
+		STATUS_IO_REPARSE_TAG_NOT_HANDLED => target::EOPNOTSUPP,
+		STATUS_REPARSE_POINT_NOT_RESOLVED => target::ELOOP,
+		STATUS_FILE_TOO_LARGE             => target::EFBIG,
+		_ => return None,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Linux error numbers that share a Windows code with another error
+	// number, and the error number that the code converts back to.
+	const WIN32_LOSSY: &[(Error, Error)] = &[
+		(target::ENXIO, target::ENODEV),
+		(target::ENFILE, target::EMFILE),
+		(target::ETXTBSY, target::EACCES),
+		(target::ETIME, target::ETIMEDOUT),
+	];
+	const NTSTATUS_LOSSY: &[(Error, Error)] = &[
+		(target::ENFILE, target::EMFILE),
+	];
+
+	fn errors() -> impl Iterator<Item = Error> {
+		(1..0x1000).filter_map(Error::new)
+	}
+
+	fn round_trip(err: Error, lossy: &[(Error, Error)]) -> Error {
+		match lossy.iter().find(|(from, _)| *from == err) {
+			Some((_, to)) => *to,
+			None => err,
+		}
+	}
+
+	#[test]
+	fn win32_round_trip() {
+		for err in errors() {
+			if let Some(code) = to_win32(err) {
+				let expect = round_trip(err, WIN32_LOSSY);
+				assert_eq!(from_win32(code), Some(expect), "{:?}", err);
+			}
+		}
+	}
+
+	#[test]
+	fn wsa_round_trip() {
+		for err in errors() {
+			if let Some(code) = to_wsa(err) {
+				assert_eq!(from_wsa(code), Some(err), "{:?}", err);
+				assert_eq!(from_win32(code as u32), Some(err), "{:?}", err);
+			}
+		}
+	}
+
+	#[test]
+	fn ntstatus_round_trip() {
+		for err in errors() {
+			if let Some(status) = to_ntstatus(err) {
+				let expect = round_trip(err, NTSTATUS_LOSSY);
+				assert_eq!(from_ntstatus(status), Some(expect), "{:?}", err);
+			}
+		}
+	}
+
+	#[test]
+	fn sharing_violation() {
+		let status = STATUS_SHARING_VIOLATION;
+		assert_eq!(from_ntstatus(status), Some(target::EBUSY));
+		assert_eq!(from_win32(ERROR_SHARING_VIOLATION), Some(target::EACCES));
+		assert_eq!(to_ntstatus(target::ETXTBSY), None);
+	}
+
+	#[test]
+	fn unknown_codes() {
+		assert_eq!(from_win32(0), None);
+		assert_eq!(from_win32(0xFFFF_FFFF), None);
+		assert_eq!(from_wsa(0), None);
+		assert_eq!(from_ntstatus(0), None);
+	}
+}